@@ -1,17 +1,4 @@
-use {glib_signal_examples::TestObject, futures::StreamExt, glib::MainLoop, glib_signal::ObjectSignalExt};
-
-async fn main_async(mainloop: MainLoop) {
-	let obj = TestObject::new();
-	let mut stream = obj.signal_stream(TestObject::SIGNAL_SOMETHING);
-
-	let arg = "hello";
-	obj.something(arg, false);
-
-	let (signal_args,) = stream.next().await.unwrap();
-	assert_eq!(signal_args, arg);
-
-	mainloop.quit();
-}
+use {glib::MainLoop, glib_signal::ObjectSignalExt, glib_signal_examples::TestObject};
 
 fn main() {
 	let mainloop = MainLoop::new(None, false);
@@ -24,7 +11,19 @@ fn main() {
 			})
 			.unwrap();
 
-			context.spawn_local(main_async(mainloop.clone()));
+			let obj = TestObject::new();
+			let arg = "hello";
+			let _task = obj
+				.signal_stream(TestObject::SIGNAL_SOMETHING)
+				.for_each_local(&context, {
+					let mainloop = mainloop.clone();
+					move |(signal_args,)| {
+						assert_eq!(signal_args, arg);
+						mainloop.quit();
+					}
+				});
+
+			obj.something(arg, false);
 
 			mainloop.run();
 		})
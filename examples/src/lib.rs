@@ -1,6 +1,6 @@
 use {
 	glib::ToValue,
-	glib_signal::{BuildSignal, BuildableSignal, DetailedSignal, Signal, SignalFlags},
+	glib_signal::{BuildSignal, BuildableSignal, DetailedSignal, Propagation, Signal, SignalFlags},
 };
 
 mod imp {
@@ -26,16 +26,63 @@ mod imp {
 
 	impl ObjectImpl for TestObject {
 		fn signals() -> &'static [Signal] {
-			static SIGNALS: OnceCell<[Signal; 2]> = OnceCell::new();
-			SIGNALS.get_or_init(|| [super::TestObjectSomething::build(), super::TestObjectNothing::build()])
+			static SIGNALS: OnceCell<[Signal; 5]> = OnceCell::new();
+			SIGNALS.get_or_init(|| {
+				[
+					super::TestObjectSomething::build(),
+					super::TestObjectNothing::build(),
+					super::TestObjectCollect::build(),
+					super::TestObjectEvent::build(),
+					super::TestObjectLabel::build(),
+				]
+			})
+		}
+	}
+}
+
+mod imp_derived {
+	use glib::subclass::{object::ObjectImpl, types::ObjectSubclass};
+
+	#[derive(Default)]
+	pub struct TestObjectDerived {}
+
+	#[glib::object_subclass]
+	impl ObjectSubclass for TestObjectDerived {
+		type ParentType = super::TestObject;
+		type Type = super::TestObjectDerived;
+
+		const NAME: &'static str = "TestObjectDerived";
+
+		fn class_init(_class: &mut Self::Class) {
+			glib_signal::override_class_handler::<super::TestObjectSomething, Self, _>(|_this, args, chain_up| {
+				chain_up(args) + 1
+			});
 		}
 	}
+
+	impl ObjectImpl for TestObjectDerived {}
+
+	impl super::TestObjectImpl for TestObjectDerived {}
 }
 
 glib::wrapper! {
 	pub struct TestObject(ObjectSubclass<imp::TestObject>);
 }
 
+pub trait TestObjectImpl: glib::subclass::object::ObjectImpl {}
+
+unsafe impl<T: TestObjectImpl> glib::subclass::types::IsSubclassable<T> for TestObject {}
+
+glib::wrapper! {
+	pub struct TestObjectDerived(ObjectSubclass<imp_derived::TestObjectDerived>) @extends TestObject;
+}
+
+impl TestObjectDerived {
+	pub fn new() -> Self {
+		glib::Object::new()
+	}
+}
+
 impl TestObject {
 	pub fn new() -> Self {
 		glib::Object::new()
@@ -58,6 +105,20 @@ impl TestObject {
 		let nothing = TestObjectNothing::signal();
 		self.emit(nothing, &[&s])
 	}
+
+	pub fn collect(&self, s: &str) -> Vec<String> {
+		use glib::ObjectExt;
+		let s = s.to_value();
+		let collect = TestObjectCollect::signal();
+		self.emit(collect, &[&s])
+	}
+
+	pub fn event(&self, n: u32) -> Propagation {
+		use glib::ObjectExt;
+		let n = n.to_value();
+		let event = TestObjectEvent::signal();
+		self.emit(event, &[&n])
+	}
 }
 
 glib_signal::def_signal! {
@@ -68,6 +129,29 @@ glib_signal::def_signal! {
 	}
 }
 
+glib_signal::def_signal! {
+	impl Notifies<"event" as TestObjectEvent> for TestObject {
+		impl BuildSignal;
+		fn(&self, u32) -> Propagation
+	}
+}
+
+glib_signal::def_signal! {
+	impl Notifies<"collect" as TestObjectCollect> for TestObject {
+		impl BuildSignal;
+		ACCUMULATOR = glib_signal::Collect;
+		fn(&self, String) -> Vec<String>
+	}
+}
+
+glib_signal::def_signal! {
+	impl Notifies<"label" as TestObjectLabel> for TestObject {
+		impl BuildSignal;
+		ACCUMULATOR = glib_signal::FirstWins;
+		fn(&self, u32) -> Option<String>
+	}
+}
+
 glib_signal::def_signal! {
 	impl Notifies<"something" as TestObjectSomething> for TestObject {
 		impl {const SIGNAL_SOMETHING};
@@ -89,16 +173,24 @@ impl DetailedSignal for TestObjectSomethingElse {
 
 impl BuildSignal for TestObjectSomething {
 	fn build() -> glib::subclass::Signal {
-		Self::builder(|b| {
-			b.accumulator(|cx, lhs, rhs| {
+		Self::signal_builder()
+			.accumulator(|cx, lhs, rhs| {
 				if cx.detail() == Some(TestObjectSomethingElse::create_detail()) {
-					*lhs = (lhs.get::<u64>().unwrap() + rhs.get::<u64>().unwrap() * 2).to_value();
+					*lhs += rhs * 2;
 				} else {
-					*lhs = (lhs.get::<u64>().unwrap() + rhs.get::<u64>().unwrap()).to_value();
+					*lhs += rhs;
 				}
 				true
 			})
 			.build()
-		})
+	}
+}
+
+glib_signal::signal_enum! {
+	#[derive(Debug, PartialEq)]
+	pub enum TestObjectSignals for TestObject {
+		Something(TestObjectSomething),
+		Nothing(TestObjectNothing),
+		Event(TestObjectEvent),
 	}
 }
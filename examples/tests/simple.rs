@@ -1,4 +1,22 @@
-use {glib_signal_examples::*, glib_signal::ObjectSignalExt};
+use {
+	futures::{executor::block_on, FutureExt, SinkExt, StreamExt},
+	gio::{prelude::*, Cancellable},
+	glib::{Cast, StaticType, ToValue},
+	glib_signal::{
+		relay, AsyncPolicy, DynamicSignal, ObjectSignalExt, OnceError, Propagation, RuntimeSignal, SignalHandles,
+		SignalStreamSet,
+	},
+	glib_signal_examples::*,
+	std::{
+		cell::{Cell, RefCell},
+		rc::Rc,
+		sync::{
+			atomic::{AtomicUsize, Ordering},
+			Arc,
+		},
+		time::Duration,
+	},
+};
 
 #[test]
 fn simple_signals() {
@@ -27,3 +45,602 @@ fn simple_signals() {
 	assert_eq!(len, u64::default());
 	obj.nothing("whooo");
 }
+
+#[test]
+fn override_class_handler() {
+	let obj = TestObjectDerived::new();
+	let parent = obj.upcast_ref::<TestObject>();
+
+	assert_eq!(parent.something("whee", false), 1);
+	parent.handle(TestObjectSomething, |_, (s,)| s.len() as u64);
+	assert_eq!(parent.something("whee", false), 4 + 1);
+	assert_eq!(parent.something("whee", true), (4 + 1) * 2);
+}
+
+#[test]
+fn accumulator() {
+	let obj = TestObject::new();
+
+	assert_eq!(obj.collect("whee"), Vec::<String>::new());
+	for i in 0..2 {
+		obj.handle(TestObjectCollect, move |_, (s,)| vec![format!("{}{}", s, i)]);
+	}
+	assert_eq!(obj.collect("whee"), ["whee0", "whee1"]);
+
+	// strings start out as NULL rather than empty
+	assert_eq!(obj.emit_signal(TestObjectLabel, (0,)), None);
+	let calls = Rc::new(Cell::new(0));
+	for label in [None, Some("first"), Some("second")] {
+		let calls = calls.clone();
+		obj.handle_local(TestObjectLabel, move |_, (n,)| {
+			calls.set(calls.get() + 1);
+			label.map(|label| format!("{}{}", label, n))
+		});
+	}
+	assert_eq!(obj.emit_signal(TestObjectLabel, (1,)), Some("first1".into()));
+	assert_eq!(calls.get(), 2);
+}
+
+glib_signal::def_signal! {
+	impl Notifies<"label" as NonNullableLabel> for TestObject {
+		fn(&self, u32) -> String
+	}
+}
+
+#[test]
+#[should_panic(expected = "must be declared as an Option")]
+fn non_nullable_return() {
+	use glib_signal::BuildableSignal;
+
+	let _ = NonNullableLabel::signal_builder();
+}
+
+#[test]
+fn propagation() {
+	let obj = TestObject::new();
+	let calls = Rc::new(Cell::new(0));
+
+	assert_eq!(obj.event(0), Propagation::Proceed);
+	for _ in 0..2 {
+		let calls = calls.clone();
+		obj.handle_local(TestObjectEvent, move |_, (n,)| {
+			calls.set(calls.get() + 1);
+			(n > 0).into()
+		});
+	}
+	assert_eq!(obj.event(0), Propagation::Proceed);
+	assert_eq!(calls.get(), 2);
+	assert_eq!(obj.event(1), Propagation::Stop);
+	assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn local_handlers() {
+	let obj = TestObject::new();
+	let bound = TestObject::new();
+	obj.handle_local(TestObjectSomething, |_, (s,)| s.len() as u64);
+	obj.handle_bound(&bound, TestObjectSomething, |_, _, (s,)| s.len() as u64);
+	assert_eq!(obj.something("whee", false), 4 * 2);
+
+	// foreign emissions skip the handlers, but still need a return value
+	let remote = obj.clone();
+	let len = std::thread::spawn(move || remote.something("whee", false))
+		.join()
+		.unwrap();
+	assert_eq!(len, 0);
+}
+
+#[test]
+fn emit_collect() {
+	let obj = TestObject::new();
+
+	for i in 0..2 {
+		obj.handle(TestObjectSomething, move |_, (s,)| s.len() as u64 + i);
+	}
+	let args = ("whee".to_owned(),);
+	assert_eq!(obj.emit_signal(TestObjectSomething, args.clone()), 4 + 5);
+	assert_eq!(obj.emit_collect(TestObjectSomething, args.clone()).unwrap(), [4, 5]);
+
+	// emissions on other instances from within a handler are kept separate
+	let other = TestObject::new();
+	other.handle(TestObjectSomething, |_, _| 100);
+	obj.handle_local(TestObjectSomething, move |_, args| other.emit_signal(TestObjectSomething, args) - 100);
+	assert_eq!(obj.emit_collect(TestObjectSomething, args).unwrap(), [4, 5, 0]);
+}
+
+#[test]
+fn emit_lazy() {
+	let obj = TestObject::new();
+	let args = || ("whooo".to_owned(),);
+
+	assert!(!obj.has_handler_pending(TestObjectNothing));
+	assert_eq!(obj.emit_lazy(TestObjectNothing, || unreachable!()), None);
+	let handle = obj.handle(TestObjectNothing, |_, _| ());
+	assert!(obj.has_handler_pending(TestObjectNothing));
+	assert_eq!(obj.emit_lazy(TestObjectNothing, args), Some(()));
+	obj.remove_handle(handle);
+	assert_eq!(obj.emit_lazy(TestObjectNothing, args), None);
+
+	// signals defined elsewhere may have a class handler
+	let cancellable = Cancellable::new();
+	let cancelled = RuntimeSignal::<Cancellable, (), glib_signal::PrimitiveValue<()>>::lookup("cancelled").unwrap();
+	assert!(!cancellable.has_handler_pending(cancelled));
+	assert_eq!(cancellable.emit_lazy(cancelled, || ()), Some(()));
+}
+
+#[test]
+fn block_handlers() {
+	let obj = TestObject::new();
+
+	let handle = obj.handle(TestObjectSomething, |_, (s,)| s.len() as u64);
+	obj.handle(TestObjectSomething, |_, (s,)| s.len() as u64);
+	{
+		let _guard = obj.block_handler(&handle);
+		assert_eq!(obj.something("whee", false), 4);
+	}
+	{
+		let guard = obj.block_handlers(TestObjectSomething);
+		assert_eq!(guard.blocked(), 2);
+		assert_eq!(obj.something("whee", false), 0);
+	}
+	assert_eq!(obj.something("whee", false), 4 * 2);
+}
+
+#[test]
+fn handler_queries() {
+	let obj = TestObject::new();
+
+	let handle = obj.handle(TestObjectSomething, |_, (s,)| s.len() as u64);
+	obj.handle(TestObjectSomethingElse, |_, (s,)| s.len() as u64);
+	assert!(obj.is_connected(&handle));
+	assert_eq!(obj.handler_count(TestObjectSomething), 2);
+	assert_eq!(obj.handler_count(TestObjectSomethingElse), 1);
+	{
+		let _guard = obj.block_handler(&handle);
+		assert_eq!(obj.handler_count(TestObjectSomething), 1);
+		assert!(obj.is_connected(&handle));
+	}
+
+	assert_eq!(obj.disconnect_all(TestObjectSomethingElse), 1);
+	assert_eq!(obj.handler_count(TestObjectSomething), 1);
+	assert_eq!(obj.disconnect_all(TestObjectSomething), 1);
+	assert!(!obj.is_connected(&handle));
+}
+
+#[test]
+fn bound_handlers() {
+	let obj = TestObject::new();
+	let bound = TestObject::new();
+	bound.handle(TestObjectSomething, |_, (s,)| s.len() as u64);
+
+	obj.handle_bound(&bound, TestObjectSomething, |_, bound, (s,)| {
+		bound.something(&s, false) + 1
+	});
+	assert_eq!(obj.something("whee", false), 4 + 1);
+	drop(bound);
+	assert_eq!(obj.handler_count(TestObjectSomething), 0);
+	assert_eq!(obj.something("whee", false), 0);
+}
+
+fn reset(cancellable: &Cancellable) {
+	// not bound by gio, since it's racy in general
+	unsafe { gio::ffi::g_cancellable_reset(cancellable.as_ptr()) }
+}
+
+#[test]
+fn cancellable() {
+	let obj = TestObject::new();
+	let cancellable = Cancellable::new();
+	let handle = obj.handle_until(&cancellable, TestObjectSomething, |_, (s,)| s.len() as u64);
+	let stream = obj.signal_stream(TestObjectSomething).with_cancellable(&cancellable);
+	assert_eq!(obj.something("whee", false), 4);
+
+	cancellable.cancel();
+	assert!(!obj.is_connected(&handle));
+	assert!(stream.is_cancelled());
+	assert_eq!(obj.handler_count(TestObjectSomething), 0);
+	assert!(matches!(block_on(stream.once()), Err(OnceError::Cancelled)));
+
+	let handle = obj.handle_until(&cancellable, TestObjectSomething, |_, (s,)| s.len() as u64);
+	assert!(!obj.is_connected(&handle));
+
+	// callbacks left connected would be invoked again
+	reset(&cancellable);
+	let handle = obj.handle_until(&cancellable, TestObjectSomething, |_, (s,)| s.len() as u64);
+	drop(obj.signal_stream(TestObjectSomething).with_cancellable(&cancellable));
+	cancellable.cancel();
+	assert!(!obj.is_connected(&handle));
+	reset(&cancellable);
+	cancellable.cancel();
+
+	let context = glib::MainContext::new();
+	context
+		.with_thread_default(|| {
+			reset(&cancellable);
+			let handle = obj.handle_until(&cancellable, TestObjectSomething, |_, (s,)| s.len() as u64);
+			let remote = cancellable.clone();
+			std::thread::spawn(move || remote.cancel()).join().unwrap();
+			assert!(obj.is_connected(&handle));
+			while context.iteration(false) {}
+			assert!(!obj.is_connected(&handle));
+		})
+		.unwrap();
+}
+
+#[test]
+fn signal_handles() {
+	let obj = TestObject::new();
+	let other = TestObject::new();
+	let handles = SignalHandles::new();
+	handles.push(&obj, obj.handle(TestObjectSomething, |_, (s,)| s.len() as u64));
+	handles.push(&other, other.handle(TestObjectNothing, |_, _| ()));
+	handles.push(&other, other.handle(TestObjectSomething, |_, (s,)| s.len() as u64));
+	assert_eq!(handles.len(), 3);
+
+	{
+		let _guard = handles.block_all();
+		assert_eq!(obj.something("whee", false), 0);
+		assert_eq!(other.something("whee", false), 0);
+	}
+	assert_eq!(other.something("whee", false), 4);
+
+	drop(other);
+	handles.disconnect_all();
+	assert!(handles.is_empty());
+	assert_eq!(obj.handler_count(TestObjectSomething), 0);
+}
+
+#[test]
+fn async_handlers() {
+	let context = glib::MainContext::new();
+	context
+		.with_thread_default(|| {
+			let run = |policy| {
+				let obj = TestObject::new();
+				let log = Rc::new(RefCell::new(Vec::new()));
+				let handle = obj.handle_async_with(policy, TestObjectNothing, {
+					let log = log.clone();
+					move |_, (s,)| {
+						let log = log.clone();
+						async move {
+							log.borrow_mut().push(format!("start {}", s));
+							glib::timeout_future(Duration::ZERO).await;
+							log.borrow_mut().push(format!("end {}", s));
+						}
+					}
+				});
+				obj.nothing("a");
+				obj.nothing("b");
+				context.block_on(glib::timeout_future(Duration::from_millis(20)));
+
+				// in-flight futures are aborted on disconnect
+				obj.nothing("c");
+				obj.remove_handle(handle);
+				context.block_on(glib::timeout_future(Duration::from_millis(20)));
+
+				let log = log.borrow().clone();
+				log
+			};
+
+			assert_eq!(run(AsyncPolicy::Unbounded), ["start a", "start b", "end a", "end b"]);
+			assert_eq!(run(AsyncPolicy::Sequential), ["start a", "end a", "start b", "end b"]);
+			assert_eq!(run(AsyncPolicy::SwitchLatest), ["start b", "end b"]);
+		})
+		.unwrap();
+}
+
+#[test]
+fn emit_async() {
+	let obj = TestObject::new();
+	obj.handle_future(TestObjectSomething, |_, (s,)| async move { s.len() as u64 });
+	obj.handle_future(TestObjectSomething, |_, (s,)| async move { s.len() as u64 * 2 });
+	obj.handle(TestObjectSomething, |_, _| 1);
+
+	let other = TestObject::new();
+	other.handle_future(TestObjectSomething, |_, _| async move { 100 });
+	obj.handle_local(TestObjectSomething, move |_, args| other.emit_signal(TestObjectSomething, args));
+
+	let results = obj.emit_async(TestObjectSomething, ("whee".into(),));
+	assert_eq!(results.len(), 2);
+	assert_eq!(block_on(results), [4, 8]);
+
+	// futures are only awaited by emit_async
+	assert_eq!(obj.something("whee", false), 1);
+}
+
+#[test]
+fn handle_on_context() {
+	let context = glib::MainContext::new();
+	let obj = TestObject::new();
+	let (tx, rx) = std::sync::mpsc::channel();
+	let guard = obj.handle_on_context(&context, TestObjectNothing, move |(s,)| tx.send(s).unwrap());
+	obj.nothing("a");
+	obj.nothing("b");
+	assert!(rx.try_recv().is_err());
+	while context.iteration(false) {}
+	assert_eq!(rx.try_iter().collect::<Vec<_>>(), ["a", "b"]);
+
+	// undelivered emissions are discarded once disconnected
+	obj.nothing("c");
+	drop(guard);
+	assert_eq!(obj.handler_count(TestObjectNothing), 0);
+	while context.iteration(false) {}
+	assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn relay_signals() {
+	let source = TestObject::new();
+	let target = TestObject::new();
+	target.handle(TestObjectSomething, |_, (s,)| s.len() as u64);
+	relay(&source, TestObjectSomething, &target, TestObjectSomething, |(s,)| {
+		(s + "!",)
+	});
+	assert_eq!(source.something("whee", false), 5);
+
+	drop(target);
+	assert_eq!(source.handler_count(TestObjectSomething), 0);
+	assert_eq!(source.something("whee", false), 0);
+}
+
+#[test]
+fn signal_sink() {
+	let obj = TestObject::new();
+	let seen = Rc::new(RefCell::new(Vec::new()));
+	obj.handle_local(TestObjectNothing, {
+		let seen = seen.clone();
+		move |_, (s,)| seen.borrow_mut().push(s)
+	});
+
+	let mut sink = obj.signal_sink(TestObjectNothing);
+	block_on(sink.send(("a".into(),))).unwrap();
+	block_on(sink.send(("b".into(),))).unwrap();
+	assert_eq!(*seen.borrow(), ["a", "b"]);
+
+	drop(obj);
+	assert!(block_on(sink.send(("c".into(),))).is_err());
+}
+
+#[test]
+fn dynamic_signals() {
+	let obj = TestObject::new();
+	let signal = DynamicSignal::lookup(TestObject::static_type(), "something").unwrap();
+	assert_eq!(signal.param_types(), [String::static_type()]);
+	assert_eq!(signal.return_type(), u64::static_type());
+	assert!(DynamicSignal::lookup(TestObject::static_type(), "nonexistent").is_err());
+
+	let mut stream = obj.signal_stream_dynamic(&signal).unwrap();
+	obj
+		.handle_dynamic(&signal, |_, args| {
+			let s: String = args[0].get().unwrap();
+			Some((s.len() as u64).to_value())
+		})
+		.unwrap();
+	let res = obj.emit_dynamic(&signal, &["whee".to_value()]).unwrap();
+	assert_eq!(res.unwrap().get::<u64>().unwrap(), 4);
+	assert!(obj.emit_dynamic(&signal, &[4u32.to_value()]).is_err());
+	assert!(obj.emit_dynamic(&signal, &[]).is_err());
+
+	let args = block_on(stream.next()).unwrap();
+	assert_eq!(args[0].get::<String>().unwrap(), "whee");
+}
+
+#[test]
+fn runtime_signals() {
+	let obj = TestObject::new();
+	let signal = RuntimeSignal::<TestObject, (String,), u64>::lookup("something").unwrap();
+	assert!(RuntimeSignal::<TestObject, (u32,), u64>::lookup("something").is_err());
+	assert!(RuntimeSignal::<TestObject, (String,), bool>::lookup("something").is_err());
+	// connecting it to a plain object would fail at runtime
+	let dynamic = DynamicSignal::lookup(TestObject::static_type(), "something").unwrap();
+	assert!(RuntimeSignal::<glib::Object, (String,), u64>::with_dynamic(&dynamic).is_err());
+
+	let mut stream = obj.signal_stream(signal);
+	obj.handle(signal, |_, (s,)| s.len() as u64);
+	assert_eq!(obj.handler_count(signal), 2);
+	assert_eq!(obj.emit_signal(signal, ("whee".into(),)), 4);
+	assert_eq!(obj.something("whee", false), 4);
+	assert_eq!(block_on(stream.next()), Some(("whee".into(),)));
+}
+
+#[test]
+fn signal_enum() {
+	let obj = TestObject::new();
+	let mut stream = TestObjectSignals::stream(&obj);
+	let once = TestObjectSignals::once(&obj);
+	assert_eq!(obj.handler_count(TestObjectSomething), 2);
+
+	obj.nothing("a");
+	obj.event(1);
+	assert_eq!(block_on(stream.next()), Some(TestObjectSignals::Nothing(("a".into(),))));
+	assert_eq!(block_on(stream.next()), Some(TestObjectSignals::Event((1,))));
+	let (res, _) = block_on(once).unwrap();
+	assert_eq!(res, TestObjectSignals::Nothing(("a".into(),)));
+
+	drop(stream);
+	assert_eq!(obj.handler_count(TestObjectSomething), 0);
+	assert_eq!(obj.handler_count(TestObjectEvent), 0);
+}
+
+#[test]
+fn signal_stream_set() {
+	let a = TestObject::new();
+	let b = TestObject::new();
+	let mut set = SignalStreamSet::new(TestObjectNothing);
+	assert!(set.insert(&a));
+	assert!(set.insert(&b));
+	assert!(!set.insert(&a));
+
+	b.nothing("b");
+	a.nothing("a");
+	assert_eq!(block_on(set.next()), Some((b.clone(), ("b".into(),))));
+	assert_eq!(block_on(set.next()), Some((a.clone(), ("a".into(),))));
+
+	assert!(set.remove(&a));
+	assert_eq!(a.handler_count(TestObjectNothing), 0);
+	drop(b);
+	assert!(set.is_empty());
+
+	let mut keyed = SignalStreamSet::new(TestObjectNothing);
+	keyed.insert_keyed(&a, 1);
+	a.nothing("a");
+	assert_eq!(block_on(keyed.next()), Some((1, ("a".into(),))));
+
+	// handlers disconnected behind the set's back must not be disconnected again
+	let stale = Arc::new(AtomicUsize::new(0));
+	let log_handler = glib::log_set_handler(Some("GLib-GObject"), glib::LogLevels::all(), false, false, {
+		let stale = stale.clone();
+		move |_, _, message| {
+			if message.contains("no handler with id") {
+				stale.fetch_add(1, Ordering::SeqCst);
+			}
+		}
+	});
+	assert_eq!(a.disconnect_all(TestObjectNothing), 1);
+	assert!(keyed.remove(&a));
+	let c = TestObject::new();
+	keyed.insert_keyed(&c, 2);
+	assert_eq!(c.disconnect_all(TestObjectNothing), 1);
+	keyed.clear();
+	glib::log_remove_handler(Some("GLib-GObject"), log_handler);
+	assert_eq!(stale.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn timed_streams() {
+	let context = glib::MainContext::new();
+	context
+		.with_thread_default(|| {
+			let obj = TestObject::new();
+			// timers either fire on the next iteration or not at all
+			let expired = Duration::ZERO;
+			let never = Duration::from_secs(60 * 60);
+			let mut debounced = obj.signal_stream(TestObjectNothing).debounce(expired);
+			let mut throttled = obj.signal_stream(TestObjectNothing).throttle(never);
+			let mut expiring = obj.signal_stream(TestObjectNothing).throttle(expired);
+			let mut sampled = obj.signal_stream(TestObjectNothing).sample(expired);
+
+			obj.nothing("a");
+			obj.nothing("b");
+			assert_eq!(throttled.next().now_or_never(), Some(Some(("a".into(),))));
+			assert_eq!(expiring.next().now_or_never(), Some(Some(("a".into(),))));
+			// the others only start their timers once polled
+			assert_eq!(debounced.next().now_or_never(), None);
+			assert_eq!(sampled.next().now_or_never(), None);
+			while context.iteration(false) {}
+			assert_eq!(debounced.next().now_or_never(), Some(Some(("b".into(),))));
+			assert_eq!(sampled.next().now_or_never(), Some(Some(("b".into(),))));
+
+			// "b" was dropped by both throttles, while "c" only arrives once one has expired
+			obj.nothing("c");
+			assert_eq!(throttled.next().now_or_never(), None);
+			assert_eq!(expiring.next().now_or_never(), Some(Some(("c".into(),))));
+			assert_eq!(debounced.next().now_or_never(), None);
+			while context.iteration(false) {}
+			assert_eq!(debounced.next().now_or_never(), Some(Some(("c".into(),))));
+			assert_eq!(throttled.next().now_or_never(), None);
+		})
+		.unwrap();
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[test]
+fn once_where_timeout() {
+	let context = glib::MainContext::new();
+	context
+		.with_thread_default(|| {
+			let obj = TestObject::new();
+			let once = obj.signal_stream(TestObjectEvent).once_where(|&(n,)| n > 1);
+			obj.event(0);
+			obj.event(1);
+			obj.event(2);
+			assert_send(&once);
+			assert_send(&obj.signal_stream(TestObjectEvent).once());
+			let ((n,), target) = context.block_on(once).unwrap();
+			assert_eq!(n, 2);
+			assert_eq!(target.upgrade(), Some(obj.clone()));
+
+			let once = obj
+				.signal_stream(TestObjectEvent)
+				.once_timeout(Duration::from_millis(20));
+			assert_eq!(context.block_on(once).unwrap_err(), OnceError::Timeout);
+			assert_eq!(obj.handler_count(TestObjectEvent), 0);
+		})
+		.unwrap();
+}
+
+#[test]
+fn finalized_objects() {
+	let obj = TestObject::new();
+	let finalized = glib_signal::finalized(&obj);
+	assert!(!finalized.is_finalized());
+	drop(glib_signal::finalized(&obj));
+	drop(obj);
+	block_on(finalized);
+
+	// weak refs are notified by dispose, while the object is still alive
+	let obj = TestObject::new();
+	let (disposed, dropped) = (glib_signal::finalized(&obj), glib_signal::finalized(&obj));
+	unsafe {
+		obj.run_dispose();
+	}
+	block_on(disposed);
+	drop(dropped);
+	drop(obj);
+
+	let obj = TestObject::new();
+	let owner = TestObject::new();
+	let mut stream = obj.signal_stream(TestObjectEvent).take_until_finalized(&owner);
+	obj.event(1);
+	assert_eq!(block_on(stream.next()), Some((1,)));
+	drop(owner);
+	obj.event(2);
+	assert!(block_on(stream.next()).is_none());
+}
+
+#[test]
+fn stream_tasks() {
+	let context = glib::MainContext::new();
+	context
+		.with_thread_default(|| {
+			let obj = TestObject::new();
+			let seen = Rc::new(RefCell::new(Vec::new()));
+			let task = obj.signal_stream(TestObjectEvent).for_each_local(&context, {
+				let seen = seen.clone();
+				move |(n,)| seen.borrow_mut().push(n)
+			});
+			obj.event(1);
+			obj.event(2);
+			while context.iteration(false) {}
+			assert_eq!(*seen.borrow(), [1, 2]);
+			drop(task);
+			assert_eq!(obj.handler_count(TestObjectEvent), 0);
+
+			let running = Rc::new(Cell::new(0));
+			let finished = Rc::new(Cell::new(0));
+			let task = obj.signal_stream(TestObjectEvent).for_each_async(&context, 2, {
+				let (running, finished) = (running.clone(), finished.clone());
+				move |_| {
+					let (running, finished) = (running.clone(), finished.clone());
+					async move {
+						running.set(running.get() + 1);
+						// outlasts the test, so only finalizing the object can end it
+						glib::timeout_future(Duration::from_secs(60 * 60)).await;
+						running.set(running.get() - 1);
+						finished.set(finished.get() + 1);
+					}
+				}
+			});
+			for n in 0..3 {
+				obj.event(n);
+			}
+			while context.iteration(false) {}
+			assert_eq!(running.get(), 2);
+			drop(obj);
+			context.block_on(task).unwrap();
+			assert_eq!(running.get(), 2);
+			assert_eq!(finished.get(), 0);
+		})
+		.unwrap();
+}
@@ -0,0 +1,157 @@
+use {
+	crate::{FromValueOption, Propagation, Signal, ToValueOption},
+	glib::{subclass::signal::SignalInvocationHint, Value},
+	std::ops::AddAssign,
+};
+
+/// An untyped accumulator, as accepted by
+/// [SignalBuilder::accumulator](glib::subclass::signal::SignalBuilder::accumulator).
+pub type AccumulatorFn = fn(&SignalInvocationHint, &mut Value, &Value) -> bool;
+
+/// Combines the return values of each handler invoked during an emission.
+///
+/// Returning `false` stops the emission.
+pub trait Accumulator<T>: Send + Sync + 'static {
+	fn accumulate(&self, hint: &SignalInvocationHint, acc: &mut T, value: T) -> bool;
+
+	/// Starts accumulating from the first value, for return types whose zero value can't be
+	/// represented as `T`, such as a `String` or an object that starts out as `NULL`.
+	///
+	/// This only happens with [accumulate] on signals that weren't built by
+	/// [BuildableSignal](crate::BuildableSignal), which requires such types to be an `Option`.
+	///
+	/// By default, the first value is used as is and the emission continues.
+	fn accumulate_first(&self, hint: &SignalInvocationHint, value: T) -> (T, bool) {
+		let _ = hint;
+		(value, true)
+	}
+}
+
+/// Adapts an accumulator function.
+pub(crate) struct FnAccumulator<F>(pub(crate) F);
+
+impl<T, F> Accumulator<T> for FnAccumulator<F>
+where
+	F: Fn(&SignalInvocationHint, &mut T, T) -> bool + Send + Sync + 'static,
+{
+	fn accumulate(&self, hint: &SignalInvocationHint, acc: &mut T, value: T) -> bool {
+		(self.0)(hint, acc, value)
+	}
+}
+
+/// The first handler to return a non-default value wins, and stops the emission.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FirstWins;
+
+impl<T: Default + PartialEq> Accumulator<T> for FirstWins {
+	fn accumulate(&self, _hint: &SignalInvocationHint, acc: &mut T, value: T) -> bool {
+		if value == T::default() {
+			true
+		} else {
+			*acc = value;
+			false
+		}
+	}
+
+	fn accumulate_first(&self, _hint: &SignalInvocationHint, value: T) -> (T, bool) {
+		let proceed = value == T::default();
+		(value, proceed)
+	}
+}
+
+/// The value returned by the last handler is used.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LastWins;
+
+impl<T> Accumulator<T> for LastWins {
+	fn accumulate(&self, _hint: &SignalInvocationHint, acc: &mut T, value: T) -> bool {
+		*acc = value;
+		true
+	}
+}
+
+/// Adds together the values returned by every handler.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Sum;
+
+impl<T: AddAssign> Accumulator<T> for Sum {
+	fn accumulate(&self, _hint: &SignalInvocationHint, acc: &mut T, value: T) -> bool {
+		*acc += value;
+		true
+	}
+}
+
+/// Stops the emission as soon as a handler returns `true`, like
+/// `g_signal_accumulator_true_handled`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TrueHandled;
+
+impl Accumulator<bool> for TrueHandled {
+	fn accumulate(&self, _hint: &SignalInvocationHint, acc: &mut bool, value: bool) -> bool {
+		*acc = value;
+		!value
+	}
+}
+
+impl Accumulator<Propagation> for TrueHandled {
+	fn accumulate(&self, _hint: &SignalInvocationHint, acc: &mut Propagation, value: Propagation) -> bool {
+		*acc = value;
+		value.is_proceed()
+	}
+}
+
+/// Concatenates the collections returned by every handler, e.g. a `Vec<String>`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Collect;
+
+impl<T: IntoIterator + Extend<T::Item>> Accumulator<T> for Collect {
+	fn accumulate(&self, _hint: &SignalInvocationHint, acc: &mut T, value: T) -> bool {
+		acc.extend(value);
+		true
+	}
+}
+
+/// Adapts the accumulator `A` to an [AccumulatorFn] for the signal `S`.
+///
+/// Usable directly with [BuildableSignal::builder](crate::BuildableSignal::builder), or via the
+/// `ACCUMULATOR = A;` line of [impl_signal](crate::impl_signal).
+pub fn accumulate<S, A>(hint: &SignalInvocationHint, lhs: &mut Value, rhs: &Value) -> bool
+where
+	S: Signal,
+	S::Return: FromValueOption,
+	A: Accumulator<<S::Return as ToValueOption>::Type> + Default,
+{
+	accumulate_value::<S::Return, A>(hint, lhs, rhs)
+}
+
+pub(crate) fn accumulate_value<R, A>(hint: &SignalInvocationHint, lhs: &mut Value, rhs: &Value) -> bool
+where
+	R: FromValueOption,
+	A: Accumulator<R::Type> + Default,
+{
+	accumulate_with::<R, _>(hint, lhs, rhs, &A::default())
+}
+
+pub(crate) fn accumulate_with<R, A>(hint: &SignalInvocationHint, lhs: &mut Value, rhs: &Value, accumulator: &A) -> bool
+where
+	R: FromValueOption,
+	A: Accumulator<R::Type>,
+{
+	// a handler connected without type checks may return anything
+	let rhs = match R::from_value_option(Some(rhs)) {
+		Ok(rhs) => rhs,
+		Err(_) => return true,
+	};
+	let (acc, res) = match R::from_value_option(Some(lhs)) {
+		Ok(mut acc) => {
+			let res = accumulator.accumulate(hint, &mut acc, rhs);
+			(acc, res)
+		},
+		// still the zero value, which can't be represented as `R` until a handler provides one
+		Err(_) => accumulator.accumulate_first(hint, rhs),
+	};
+	if let Some(acc) = acc.into().to_value_option() {
+		*lhs = acc;
+	}
+	res
+}
@@ -0,0 +1,83 @@
+use {
+	glib::{JoinHandle, MainContext},
+	std::{cell::RefCell, collections::VecDeque, future::Future, pin::Pin, rc::Rc},
+};
+
+/// How [handle_async_with](crate::ObjectSignalExt::handle_async_with) runs the futures of
+/// overlapping emissions.
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AsyncPolicy {
+	/// Run the future of every emission concurrently.
+	Unbounded,
+	/// Queue each future until the ones before it have completed.
+	Sequential,
+	/// Abort any future that is still running when the signal is emitted again.
+	SwitchLatest,
+}
+
+impl Default for AsyncPolicy {
+	fn default() -> Self {
+		Self::Unbounded
+	}
+}
+
+pub(crate) type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Spawns the futures of an async handler, aborting them once the handler is disconnected.
+pub(crate) struct AsyncHandler {
+	policy: AsyncPolicy,
+	context: MainContext,
+	tasks: RefCell<Vec<JoinHandle<()>>>,
+	queue: Rc<RefCell<VecDeque<LocalFuture>>>,
+}
+
+impl AsyncHandler {
+	/// Spawns onto the thread-default main context at the time of creation.
+	pub(crate) fn new(policy: AsyncPolicy) -> Self {
+		Self {
+			policy,
+			context: MainContext::ref_thread_default(),
+			tasks: Default::default(),
+			queue: Default::default(),
+		}
+	}
+
+	pub(crate) fn spawn(&self, future: LocalFuture) {
+		let mut tasks = self.tasks.borrow_mut();
+		tasks.retain(|task| !task.source().is_destroyed());
+		match self.policy {
+			AsyncPolicy::Unbounded => tasks.push(self.context.spawn_local(future)),
+			AsyncPolicy::SwitchLatest => {
+				for task in tasks.drain(..) {
+					task.abort();
+				}
+				tasks.push(self.context.spawn_local(future));
+			},
+			AsyncPolicy::Sequential => {
+				self.queue.borrow_mut().push_back(future);
+				// a single task drains the queue, and is only spawned when none is running
+				if tasks.is_empty() {
+					let queue = self.queue.clone();
+					tasks.push(self.context.spawn_local(async move {
+						loop {
+							let next = queue.borrow_mut().pop_front();
+							match next {
+								Some(next) => next.await,
+								None => break,
+							}
+						}
+					}));
+				}
+			},
+		}
+	}
+}
+
+impl Drop for AsyncHandler {
+	fn drop(&mut self) {
+		for task in self.tasks.get_mut().drain(..) {
+			task.abort();
+		}
+	}
+}
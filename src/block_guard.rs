@@ -0,0 +1,88 @@
+use {
+	crate::{ConnectDetails, ObjectSignalExt},
+	glib::{
+		gobject_ffi,
+		translate::{from_glib, IntoGlib, ToGlibPtr},
+		Cast, ObjectType, SignalHandlerId,
+	},
+	std::{os::raw::c_ulong, ptr},
+};
+
+/// Keeps signal handlers blocked until dropped.
+///
+/// Created by [ObjectSignalExt::block_handler](crate::ObjectSignalExt::block_handler) and
+/// [ObjectSignalExt::block_handlers](crate::ObjectSignalExt::block_handlers).
+#[must_use]
+#[derive(Debug)]
+pub struct BlockGuard<'a, O: ObjectType> {
+	target: &'a O,
+	handlers: Vec<c_ulong>,
+}
+
+impl<'a, O: ObjectType> BlockGuard<'a, O> {
+	pub fn block_handler(target: &'a O, handle: &SignalHandlerId) -> Self {
+		let handle = unsafe { handle.as_raw() };
+		unsafe {
+			gobject_ffi::g_signal_handler_block(target.as_object_ref().to_glib_none().0, handle);
+		}
+		Self {
+			target,
+			handlers: vec![handle],
+		}
+	}
+
+	/// Blocks every unblocked handler connected to `signal`.
+	///
+	/// Only handlers connected with the same detail are matched if `signal` specifies one.
+	pub fn block_matched(target: &'a O, signal: &ConnectDetails) -> Self {
+		let instance = target.as_object_ref().to_glib_none().0;
+		let mut handlers = Vec::new();
+		loop {
+			let handle = unsafe {
+				gobject_ffi::g_signal_handler_find(
+					instance,
+					signal.match_mask() | gobject_ffi::G_SIGNAL_MATCH_UNBLOCKED,
+					signal.signal().into_glib(),
+					signal.detail().map(|q| q.into_glib()).unwrap_or(0),
+					ptr::null_mut(),
+					ptr::null_mut(),
+					ptr::null_mut(),
+				)
+			};
+			match handle {
+				0 => break,
+				handle => unsafe {
+					gobject_ffi::g_signal_handler_block(instance, handle);
+					handlers.push(handle);
+				},
+			}
+		}
+		Self { target, handlers }
+	}
+
+	pub fn target(&self) -> &'a O {
+		self.target
+	}
+
+	/// The number of handlers blocked by this guard.
+	pub fn blocked(&self) -> usize {
+		self.handlers.len()
+	}
+}
+
+impl<'a, O: ObjectType> Drop for BlockGuard<'a, O> {
+	fn drop(&mut self) {
+		let instance = self.target.as_object_ref().to_glib_none().0;
+		for &handle in &self.handlers {
+			unsafe {
+				if self
+					.target
+					.unsafe_cast_ref::<glib::Object>()
+					.is_connected(&from_glib(handle))
+				{
+					gobject_ffi::g_signal_handler_unblock(instance, handle);
+				}
+			}
+		}
+	}
+}
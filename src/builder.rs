@@ -0,0 +1,125 @@
+use {
+	crate::{
+		accumulator::{accumulate_with, FnAccumulator},
+		class_handler,
+		collect::{self, BoxedAccumulator},
+		Accumulator, BorrowedObject, FromValueOption, FromValues, Signal, ToValueOption,
+	},
+	glib::{
+		subclass::signal::{SignalBuilder, SignalInvocationHint},
+		value::FromValue,
+		Value,
+	},
+	std::marker::PhantomData,
+};
+
+/// A [SignalBuilder] that accepts handlers typed according to `S`.
+///
+/// Obtained from [BuildableSignal::signal_builder](crate::BuildableSignal::signal_builder).
+#[must_use]
+pub struct TypedSignalBuilder<S> {
+	builder: SignalBuilder,
+	accumulator: Option<BoxedAccumulator>,
+	/// Whether a class handler was set, or may have been through [map](Self::map).
+	class_handler: bool,
+	_signal: PhantomData<S>,
+}
+
+impl<S: Signal> TypedSignalBuilder<S> {
+	/// Wraps `builder`, using [Signal::ACCUMULATOR](crate::Signal::ACCUMULATOR) if one is set.
+	pub fn new(builder: SignalBuilder) -> Self {
+		Self {
+			builder,
+			accumulator: S::ACCUMULATOR.map(|accumulator| Box::new(accumulator) as BoxedAccumulator),
+			class_handler: false,
+			_signal: PhantomData,
+		}
+	}
+
+	/// Sets the default handler of the signal.
+	pub fn class_handler<F>(self, class_handler: F) -> Self
+	where
+		F: Fn(&S::Object, S::Arguments) -> <S::Return as ToValueOption>::Type + Send + Sync + 'static,
+		for<'a> BorrowedObject<'a, S::Object>: FromValue<'a>,
+	{
+		self.map(|builder| {
+			builder.class_handler(move |_, values| {
+				let (this, args) = values.split_first().unwrap();
+				let this: BorrowedObject<S::Object> = this.get().unwrap();
+				let args = S::Arguments::from_values(args).unwrap();
+				class_handler(&this, args).into().to_value_option()
+			})
+		})
+	}
+
+	/// Sets the accumulator used to combine the return values of each handler.
+	///
+	/// The accumulated value starts out as the zero value of the signal's return type.
+	pub fn accumulator<F>(self, accumulator: F) -> Self
+	where
+		F: Fn(&SignalInvocationHint, &mut <S::Return as ToValueOption>::Type, <S::Return as ToValueOption>::Type) -> bool
+			+ Send
+			+ Sync
+			+ 'static,
+		S::Return: FromValueOption,
+	{
+		self.with_accumulator(FnAccumulator(accumulator))
+	}
+
+	/// Sets an untyped accumulator.
+	///
+	/// Use this instead of setting one via [map](Self::map), which would prevent
+	/// [emit_collect](crate::ObjectSignalExt::emit_collect) from working with this signal.
+	pub fn raw_accumulator<F>(mut self, accumulator: F) -> Self
+	where
+		F: Fn(&SignalInvocationHint, &mut Value, &Value) -> bool + Send + Sync + 'static,
+	{
+		self.accumulator = Some(Box::new(accumulator));
+		self
+	}
+
+	/// Sets one of the standard [accumulators](crate::Accumulator), such as [Sum](crate::Sum).
+	///
+	/// See [Accumulator::accumulate_first] for return types without a representable zero value.
+	pub fn with_accumulator<A>(self, accumulator: A) -> Self
+	where
+		A: Accumulator<<S::Return as ToValueOption>::Type>,
+		S::Return: FromValueOption,
+	{
+		self.raw_accumulator(move |hint, lhs, rhs| accumulate_with::<S::Return, _>(hint, lhs, rhs, &accumulator))
+	}
+
+	/// Applies untyped options to the underlying [SignalBuilder].
+	///
+	/// The signal is assumed to have a class handler afterwards, which
+	/// [emit_lazy](crate::ObjectSignalExt::emit_lazy) can't rule out.
+	pub fn map<F: FnOnce(SignalBuilder) -> SignalBuilder>(mut self, f: F) -> Self {
+		self.builder = f(self.builder);
+		self.class_handler = true;
+		self
+	}
+
+	/// Unwraps the underlying [SignalBuilder], including the accumulator.
+	///
+	/// As options can still be applied to it, [emit_lazy](crate::ObjectSignalExt::emit_lazy)
+	/// assumes the signal has a class handler, unlike with [build](Self::build).
+	pub fn into_inner(self) -> SignalBuilder {
+		let accumulator = self.accumulator;
+		self
+			.builder
+			.accumulator(move |hint, lhs, rhs| collect::accumulate::<S>(hint, lhs, rhs, accumulator.as_ref()))
+	}
+
+	pub fn build(self) -> glib::subclass::Signal {
+		if !self.class_handler {
+			class_handler::register_without_class_handler::<S>();
+		}
+		self.into_inner().build()
+	}
+}
+
+impl<S: Signal> From<TypedSignalBuilder<S>> for SignalBuilder {
+	fn from(builder: TypedSignalBuilder<S>) -> Self {
+		builder.into_inner()
+	}
+}
@@ -0,0 +1,139 @@
+use {
+	crate::ObjectSignalExt,
+	gio::{prelude::*, Cancellable},
+	glib::{
+		g_warning, gobject_ffi,
+		thread_guard::{self, ThreadGuard},
+		translate::{from_glib, from_glib_none, ToGlibPtr},
+		MainContext, ObjectType, SignalHandlerId, WeakRef,
+	},
+	std::{
+		cell::RefCell,
+		mem,
+		os::raw::c_ulong,
+		rc::Rc,
+		sync::{
+			atomic::{AtomicUsize, Ordering},
+			Arc,
+		},
+	},
+};
+
+/// Disconnects a signal handler when a [Cancellable] is triggered.
+///
+/// The cancellable's callback only holds onto the target weakly. When triggered from another
+/// thread, the disconnect is dispatched to the thread-default main context of the thread that
+/// connected it.
+#[derive(Debug)]
+pub(crate) struct CancelHandle {
+	cancellable: Cancellable,
+	id: Option<c_ulong>,
+	/// One more than the id of the thread currently running the callback, or zero.
+	running: Arc<AtomicUsize>,
+}
+
+impl CancelHandle {
+	pub(crate) fn disconnect_on_cancel<O: ObjectType>(
+		target: &O,
+		handle: &SignalHandlerId,
+		cancellable: &Cancellable,
+	) -> Self {
+		let target: ThreadGuard<WeakRef<glib::Object>> = unsafe {
+			let target: glib::Object = from_glib_none(target.as_object_ref().to_glib_none().0);
+			ThreadGuard::new(target.downgrade())
+		};
+		let handle = unsafe { handle.as_raw() };
+		let context = MainContext::ref_thread_default();
+		let running = Arc::new(AtomicUsize::new(0));
+		let id = cancellable.connect_cancelled({
+			let running = running.clone();
+			move |_| {
+				running.store(thread_guard::thread_id() + 1, Ordering::SeqCst);
+				match target.is_owner() {
+					true => disconnect_owned(target, handle),
+					false => context.invoke(move || disconnect_owned(target, handle)),
+				}
+				running.store(0, Ordering::SeqCst);
+			}
+		});
+		Self {
+			cancellable: cancellable.clone(),
+			id: id.map(|id| unsafe { id.as_raw() }),
+			running,
+		}
+	}
+
+	#[cfg(feature = "futures")]
+	pub(crate) fn is_cancelled(&self) -> bool {
+		self.cancellable.is_cancelled()
+	}
+}
+
+impl Drop for CancelHandle {
+	fn drop(&mut self) {
+		let id = match self.id.take() {
+			Some(id) => id,
+			None => return,
+		};
+		let cancellable = self.cancellable.to_glib_none().0;
+		unsafe {
+			match self.running.load(Ordering::SeqCst) == thread_guard::thread_id() + 1 {
+				// g_cancellable_disconnect would wait for the callback we're being dropped from
+				true => gobject_ffi::g_signal_handler_disconnect(cancellable as *mut _, id),
+				false => gio::ffi::g_cancellable_disconnect(cancellable, id),
+			}
+		}
+	}
+}
+
+/// A handler callback that owns the [CancelHandle] disconnecting it, so that both are dropped
+/// together.
+///
+/// The cancel handle can only be created once the handler has been connected, so it is filled in
+/// through the slot returned by [new](UntilCancelled::new).
+pub(crate) struct UntilCancelled<C> {
+	callback: C,
+	// only held so that it is dropped along with the handler
+	_cancel: Rc<RefCell<Option<CancelHandle>>>,
+}
+
+impl<C> UntilCancelled<C> {
+	pub(crate) fn new(callback: C) -> (Self, Rc<RefCell<Option<CancelHandle>>>) {
+		let cancel = Rc::new(RefCell::new(None));
+		let this = Self {
+			callback,
+			_cancel: cancel.clone(),
+		};
+		(this, cancel)
+	}
+
+	pub(crate) fn call<O, A, R>(&self, this: &O, args: A) -> R
+	where
+		C: Fn(&O, A) -> R,
+	{
+		(self.callback)(this, args)
+	}
+}
+
+fn disconnect_owned(target: ThreadGuard<WeakRef<glib::Object>>, handle: c_ulong) {
+	if !target.is_owner() {
+		g_warning!(
+			"glib-signal",
+			"cancelled signal handler {} from a foreign main context",
+			handle
+		);
+		// the weak ref can't be released here either
+		mem::forget(target);
+		return
+	}
+	if let Some(target) = target.get_ref().upgrade() {
+		disconnect(&target, handle)
+	}
+}
+
+fn disconnect(target: &glib::Object, handle: c_ulong) {
+	let handle: SignalHandlerId = unsafe { from_glib(handle) };
+	if target.is_connected(&handle) {
+		target.disconnect(handle);
+	}
+}
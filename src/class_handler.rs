@@ -0,0 +1,110 @@
+use {
+	crate::{
+		value_option::check_zero_value, BorrowedObject, FromValueOption, FromValues, Signal, ToValueOption, ToValues,
+	},
+	glib::{
+		once_cell::sync::Lazy,
+		subclass::{types::ObjectSubclass, SignalId},
+		translate::{IntoGlib, ToGlibPtr, ToGlibPtrMut},
+		value::FromValue,
+		Closure, IsA, StaticType, Type, Value,
+	},
+	std::{collections::HashSet, ptr, sync::Mutex},
+};
+
+/// Signals built by [TypedSignalBuilder](crate::TypedSignalBuilder) without a default class
+/// handler.
+///
+/// Emitting any other signal may run a class handler, which
+/// [emit_lazy](crate::ObjectSignalExt::emit_lazy) can't detect through
+/// `g_signal_has_handler_pending`.
+static WITHOUT_CLASS_HANDLER: Lazy<Mutex<HashSet<(Type, &'static str)>>> = Lazy::new(Default::default);
+
+pub(crate) fn register_without_class_handler<S: Signal>() {
+	WITHOUT_CLASS_HANDLER
+		.lock()
+		.unwrap()
+		.insert((S::Object::static_type(), S::NAME));
+}
+
+pub(crate) fn lacks_class_handler(signal: SignalId) -> bool {
+	let query = signal.query();
+	WITHOUT_CLASS_HANDLER
+		.lock()
+		.unwrap()
+		.contains(&(query.type_(), query.signal_name()))
+}
+
+/// Overrides the class handler of `S` for instances of the subclass `T`.
+///
+/// `class_handler` receives the emitting instance, the signal arguments, and a `chain_up`
+/// callback that invokes the parent class' handler with the provided arguments.
+///
+/// This must be called during class initialization, i.e. from
+/// [ObjectSubclass::class_init](glib::subclass::types::ObjectSubclass::class_init). Like
+/// [BuildableSignal::signal_builder](crate::BuildableSignal::signal_builder), it panics if the
+/// signal's return type starts out as `NULL` but isn't declared as an `Option`.
+pub fn override_class_handler<S, T, F>(class_handler: F)
+where
+	S: Signal,
+	T: ObjectSubclass,
+	T::Type: IsA<S::Object>,
+	S::Arguments: ToValues,
+	S::Return: FromValueOption,
+	for<'a> BorrowedObject<'a, T::Type>: FromValue<'a>,
+	F: Fn(
+			&T::Type,
+			S::Arguments,
+			&dyn Fn(S::Arguments) -> <S::Return as ToValueOption>::Type,
+		) -> <S::Return as ToValueOption>::Type
+		+ Send
+		+ Sync
+		+ 'static,
+{
+	let signal = S::signal();
+	let return_type = signal.query().return_type().type_();
+	if let Err(e) = check_zero_value::<S::Return>(return_type) {
+		panic!("{}: {}", S::NAME, e)
+	}
+	let closure = Closure::new(move |values| {
+		let (this, args) = values.split_first().unwrap();
+		let instance: BorrowedObject<T::Type> = this.get().unwrap();
+		let args = S::Arguments::from_values(args).unwrap();
+		let chain_up = |args: S::Arguments| unsafe { chain_from_overridden::<S>(this, return_type, &args) };
+		class_handler(&instance, args, &chain_up).into().to_value_option()
+	});
+
+	unsafe {
+		glib::gobject_ffi::g_signal_override_class_closure(
+			signal.into_glib(),
+			T::type_().into_glib(),
+			closure.to_glib_none().0,
+		);
+	}
+}
+
+/// Must only be called from within an overridden class handler of `S`.
+unsafe fn chain_from_overridden<S>(
+	this: &Value,
+	return_type: Type,
+	args: &S::Arguments,
+) -> <S::Return as ToValueOption>::Type
+where
+	S: Signal,
+	S::Arguments: ToValues,
+	S::Return: FromValueOption,
+{
+	let values: Vec<Value> = Some(this.clone())
+		.into_iter()
+		.chain(args.to_values().as_ref().iter().cloned())
+		.collect();
+	let mut res = match return_type {
+		ty if ty == Type::UNIT => None,
+		ty => Some(Value::from_type(ty)),
+	};
+	glib::gobject_ffi::g_signal_chain_from_overridden(values.as_ptr() as *const _, match &mut res {
+		Some(res) => res.to_glib_none_mut().0,
+		None => ptr::null_mut(),
+	});
+	S::Return::from_value_option(res.as_ref()).expect(S::NAME)
+}
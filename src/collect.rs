@@ -0,0 +1,50 @@
+use {
+	crate::{
+		emission_frame::{self, Frame},
+		Signal,
+	},
+	glib::{
+		subclass::{signal::SignalInvocationHint, SignalId},
+		Value,
+	},
+	std::cell::RefCell,
+};
+
+pub(crate) type BoxedAccumulator = Box<dyn Fn(&SignalInvocationHint, &mut Value, &Value) -> bool + Send + Sync>;
+
+thread_local! {
+	static FRAMES: RefCell<Vec<Frame<Value>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Installed by [TypedSignalBuilder](crate::TypedSignalBuilder) for every signal it builds, so
+/// that [emit_collect](crate::ObjectSignalExt::emit_collect) can observe each handler's return
+/// value before passing it on to the signal's own accumulator.
+pub(crate) fn accumulate<S: Signal>(
+	hint: &SignalInvocationHint,
+	lhs: &mut Value,
+	rhs: &Value,
+	accumulator: Option<&BoxedAccumulator>,
+) -> bool {
+	emission_frame::push(&FRAMES, S::signal(), rhs.clone());
+
+	match accumulator {
+		Some(accumulator) => accumulator(hint, lhs, rhs),
+		None => {
+			*lhs = rhs.clone();
+			true
+		},
+	}
+}
+
+/// Runs `emit` while collecting the values returned from the handlers of `signal`.
+///
+/// Nothing is collected if the signal was not built by
+/// [TypedSignalBuilder](crate::TypedSignalBuilder).
+pub(crate) fn collect<F: FnOnce()>(signal: SignalId, emit: F) -> Vec<Value> {
+	emission_frame::collect(&FRAMES, signal, emit).1
+}
+
+/// Runs `emit` without letting an enclosing [collect] of `signal` observe its handlers.
+pub(crate) fn isolate<R, F: FnOnce() -> R>(signal: SignalId, emit: F) -> R {
+	emission_frame::collect(&FRAMES, signal, emit).0
+}
@@ -0,0 +1,71 @@
+use {
+	futures_channel::mpsc,
+	futures_core::{ready, Stream},
+	glib::{g_warning, MainContext},
+	std::{
+		future::Future,
+		pin::Pin,
+		sync::{
+			atomic::{AtomicBool, Ordering},
+			Arc,
+		},
+		task::{Context, Poll},
+	},
+};
+
+/// The emitting side of [handle_on_context](crate::ObjectSignalExt::handle_on_context), owned by
+/// the signal handler.
+///
+/// Dropping it along with the handler stops any emissions that haven't been delivered yet.
+pub(crate) struct Forwarder<T> {
+	tx: mpsc::UnboundedSender<T>,
+	active: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> Forwarder<T> {
+	/// Invokes `callback` on `context` for every value sent, in order.
+	pub(crate) fn spawn<F: Fn(T) + Send + 'static>(context: &MainContext, callback: F) -> Self {
+		let (tx, rx) = mpsc::unbounded();
+		let active = Arc::new(AtomicBool::new(true));
+		context.spawn(Forward {
+			rx,
+			active: active.clone(),
+			callback,
+		});
+		Self { tx, active }
+	}
+
+	pub(crate) fn send(&self, value: T) {
+		if let Err(e) = self.tx.unbounded_send(value) {
+			g_warning!("glib-signal", "Failed to forward signal: {:?}", e.into_send_error());
+		}
+	}
+}
+
+impl<T> Drop for Forwarder<T> {
+	fn drop(&mut self) {
+		self.active.store(false, Ordering::Release);
+	}
+}
+
+struct Forward<T, F> {
+	rx: mpsc::UnboundedReceiver<T>,
+	active: Arc<AtomicBool>,
+	callback: F,
+}
+
+impl<T, F: Fn(T)> Future for Forward<T, F> {
+	type Output = ();
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		loop {
+			match ready!(Pin::new(&mut this.rx).poll_next(cx)) {
+				Some(value) if this.active.load(Ordering::Acquire) => (this.callback)(value),
+				_ => return Poll::Ready(()),
+			}
+		}
+	}
+}
+
+impl<T, F> Unpin for Forward<T, F> {}
@@ -0,0 +1,128 @@
+use {
+	crate::ConnectDetails,
+	glib::{BoolError, Type, Value},
+};
+
+/// A signal that is only known at runtime, along with its types from `g_signal_query`.
+///
+/// Used with [handle_dynamic](crate::ObjectSignalExt::handle_dynamic) and
+/// [emit_dynamic](crate::ObjectSignalExt::emit_dynamic) in place of a static
+/// [Signal](crate::Signal).
+#[derive(Clone, Debug)]
+pub struct DynamicSignal {
+	details: ConnectDetails,
+	name: &'static str,
+	object_type: Type,
+	param_types: Vec<Type>,
+	return_type: Type,
+}
+
+impl DynamicSignal {
+	/// Looks up a signal of `object_type` by name, which may include a `::detail`.
+	pub fn lookup(object_type: Type, name: &str) -> Result<Self, BoolError> {
+		let (signal, detail) = glib::subclass::SignalId::parse_name(name, object_type, false)
+			.ok_or_else(|| glib::bool_error!("unknown signal {:?} of type {:?}", name, object_type))?;
+		Ok(Self::with_details(unsafe {
+			ConnectDetails::with_parts(signal, detail, false)
+		}))
+	}
+
+	pub fn with_details(details: ConnectDetails) -> Self {
+		let query = details.signal().query();
+		Self {
+			details,
+			name: query.signal_name(),
+			object_type: query.type_(),
+			param_types: query.param_types().iter().map(|ty| ty.type_()).collect(),
+			return_type: query.return_type().type_(),
+		}
+	}
+
+	pub fn with_after(mut self, run_after: bool) -> Self {
+		self.details.run_after = run_after;
+		self
+	}
+
+	pub fn details(&self) -> &ConnectDetails {
+		&self.details
+	}
+
+	pub fn name(&self) -> &'static str {
+		self.name
+	}
+
+	/// The type that declared the signal.
+	pub fn object_type(&self) -> Type {
+		self.object_type
+	}
+
+	pub fn param_types(&self) -> &[Type] {
+		&self.param_types
+	}
+
+	/// [Type::UNIT] if the signal doesn't return anything.
+	pub fn return_type(&self) -> Type {
+		self.return_type
+	}
+
+	/// Whether the signal can be connected to or emitted on an instance of `ty`.
+	pub fn check_object(&self, ty: Type) -> Result<(), BoolError> {
+		match ty.is_a(self.object_type) {
+			true => Ok(()),
+			false => Err(glib::bool_error!(
+				"signal {:?} of type {:?} does not belong to {:?}",
+				self.name,
+				self.object_type,
+				ty
+			)),
+		}
+	}
+
+	/// Whether `args` match the signal's parameter types, not including the instance.
+	pub fn check_args(&self, args: &[Value]) -> Result<(), BoolError> {
+		if args.len() != self.param_types.len() {
+			return Err(glib::bool_error!(
+				"signal {:?} expects {} arguments, got {}",
+				self.name,
+				self.param_types.len(),
+				args.len()
+			))
+		}
+		match args
+			.iter()
+			.zip(&self.param_types)
+			.position(|(arg, ty)| !arg.type_().is_a(*ty))
+		{
+			Some(i) => Err(glib::bool_error!(
+				"signal {:?} expects argument {} to be {:?}, got {:?}",
+				self.name,
+				i,
+				self.param_types[i],
+				args[i].type_()
+			)),
+			None => Ok(()),
+		}
+	}
+
+	/// Whether `value` matches the signal's return type.
+	pub fn check_return(&self, value: Option<&Value>) -> Result<(), BoolError> {
+		match value {
+			None if self.return_type == Type::UNIT => Ok(()),
+			Some(value) if self.return_type != Type::UNIT && value.type_().is_a(self.return_type) => Ok(()),
+			value => Err(glib::bool_error!(
+				"signal {:?} expects a return value of {:?}, got {:?}",
+				self.name,
+				self.return_type,
+				value.map(|v| v.type_())
+			)),
+		}
+	}
+
+	/// The value handlers return when they have nothing better to return.
+	pub(crate) fn default_return(&self) -> Option<Value> {
+		match self.return_type {
+			Type::UNIT => None,
+			ty => Some(Value::from_type(ty)),
+		}
+	}
+}
@@ -0,0 +1,63 @@
+use {
+	glib::subclass::SignalId,
+	std::{cell::RefCell, mem, thread::LocalKey},
+};
+
+pub(crate) struct Frame<T> {
+	signal: SignalId,
+	items: Vec<T>,
+}
+
+/// A thread-local stack of frames, one per emission currently being collected from.
+///
+/// Nested emissions of the same signal each get their own frame, and only the innermost one
+/// receives items.
+pub(crate) type FrameStack<T> = LocalKey<RefCell<Vec<Frame<T>>>>;
+
+/// Hands `item` to the innermost frame, if it belongs to `signal`.
+pub(crate) fn push<T: 'static>(frames: &'static FrameStack<T>, signal: SignalId, item: T) {
+	frames.with(|frames| {
+		let mut frames = frames.borrow_mut();
+		if let Some(frame) = frames.last_mut() {
+			if frame.signal == signal {
+				frame.items.push(item);
+			}
+		}
+	})
+}
+
+struct FrameGuard<T: 'static>(&'static FrameStack<T>);
+
+impl<T> FrameGuard<T> {
+	fn pop(self) -> Frame<T> {
+		let frame = self.0.with(|frames| frames.borrow_mut().pop()).unwrap();
+		mem::forget(self);
+		frame
+	}
+}
+
+impl<T> Drop for FrameGuard<T> {
+	fn drop(&mut self) {
+		self.0.with(|frames| frames.borrow_mut().pop());
+	}
+}
+
+/// Runs `emit` within a new frame for `signal`, returning its result and the items pushed to the
+/// frame.
+///
+/// The frame is popped even if `emit` unwinds.
+pub(crate) fn collect<T: 'static, R, F: FnOnce() -> R>(
+	frames: &'static FrameStack<T>,
+	signal: SignalId,
+	emit: F,
+) -> (R, Vec<T>) {
+	frames.with(|frames| {
+		frames.borrow_mut().push(Frame {
+			signal,
+			items: Vec::new(),
+		})
+	});
+	let guard = FrameGuard(frames);
+	let res = emit();
+	(res, guard.pop().items)
+}
@@ -0,0 +1,107 @@
+use {
+	crate::emission_frame::{self, Frame},
+	glib::subclass::SignalId,
+	std::{
+		any::Any,
+		cell::RefCell,
+		fmt,
+		future::Future,
+		mem,
+		pin::Pin,
+		task::{Context, Poll},
+	},
+};
+
+pub(crate) type LocalFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+thread_local! {
+	static FRAMES: RefCell<Vec<Frame<Box<dyn Any>>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Hands a future returned by a [handle_future](crate::ObjectSignalExt::handle_future) handler
+/// to the innermost [emit_async](crate::ObjectSignalExt::emit_async) of `signal`.
+///
+/// The future is dropped without being polled if the signal was emitted some other way.
+pub(crate) fn submit<T: 'static>(signal: SignalId, future: LocalFuture<T>) {
+	emission_frame::push(&FRAMES, signal, Box::new(future) as Box<dyn Any>)
+}
+
+/// Runs `emit` while collecting the futures submitted by the handlers of `signal`.
+pub(crate) fn collect<T: 'static, F: FnOnce()>(signal: SignalId, emit: F) -> EmitFuture<T> {
+	let futures = emission_frame::collect(&FRAMES, signal, emit)
+		.1
+		.into_iter()
+		.filter_map(|future| future.downcast::<LocalFuture<T>>().ok())
+		.map(|future| Pending::Running(*future))
+		.collect();
+	EmitFuture { futures }
+}
+
+/// Runs `emit` without letting an enclosing [collect] of `signal` receive the futures of its
+/// handlers.
+pub(crate) fn isolate<R, F: FnOnce() -> R>(signal: SignalId, emit: F) -> R {
+	emission_frame::collect(&FRAMES, signal, emit).0
+}
+
+enum Pending<T> {
+	Running(LocalFuture<T>),
+	Ready(T),
+	Taken,
+}
+
+/// Resolves to the results of every future returned by the handlers of an
+/// [emit_async](crate::ObjectSignalExt::emit_async) emission, in the order they were invoked.
+#[must_use]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+pub struct EmitFuture<T> {
+	futures: Vec<Pending<T>>,
+}
+
+impl<T> EmitFuture<T> {
+	/// The number of handlers that returned a future.
+	pub fn len(&self) -> usize {
+		self.futures.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.futures.is_empty()
+	}
+}
+
+impl<T> Future for EmitFuture<T> {
+	type Output = Vec<T>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		let mut ready = true;
+		for pending in &mut this.futures {
+			if let Pending::Running(future) = pending {
+				match future.as_mut().poll(cx) {
+					Poll::Ready(res) => *pending = Pending::Ready(res),
+					Poll::Pending => ready = false,
+				}
+			}
+		}
+		if !ready {
+			return Poll::Pending
+		}
+		Poll::Ready(
+			this
+				.futures
+				.iter_mut()
+				.filter_map(|pending| match mem::replace(pending, Pending::Taken) {
+					Pending::Ready(res) => Some(res),
+					_ => None,
+				})
+				.collect(),
+		)
+	}
+}
+
+impl<T> Unpin for EmitFuture<T> {}
+
+impl<T> fmt::Debug for EmitFuture<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("EmitFuture").field("len", &self.len()).finish()
+	}
+}
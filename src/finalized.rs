@@ -0,0 +1,166 @@
+use {
+	futures_channel::oneshot,
+	futures_core::{FusedFuture, FusedStream, Stream},
+	glib::{gobject_ffi, translate::ToGlibPtr, Cast, ObjectExt, ObjectType, WeakRef},
+	std::{
+		fmt,
+		future::Future,
+		marker::PhantomData,
+		pin::Pin,
+		sync::{Arc, Mutex, PoisonError},
+		task::{Context, Poll},
+	},
+};
+
+/// Shared between a [Finalized] and its weak ref, which owns one reference to it.
+///
+/// The sender is taken by whichever side gets to it first.
+type Notify = Mutex<Option<oneshot::Sender<()>>>;
+
+unsafe extern "C" fn weak_notify(data: glib::ffi::gpointer, _object: *mut gobject_ffi::GObject) {
+	let notify = Arc::from_raw(data as *const Notify);
+	let tx = notify.lock().unwrap_or_else(PoisonError::into_inner).take();
+	if let Some(tx) = tx {
+		let _ = tx.send(());
+	}
+}
+
+/// Resolves once an object has been finalized.
+///
+/// Created by [finalized].
+#[must_use]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+pub struct Finalized {
+	rx: oneshot::Receiver<()>,
+	target: WeakRef<glib::Object>,
+	notify: Arc<Notify>,
+	// the target may not be thread-safe, and is upgraded on drop
+	_thread: PhantomData<*const ()>,
+}
+
+/// A future that resolves once `target` has been finalized, using `g_object_weak_ref`.
+///
+/// Doesn't keep `target` alive.
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+pub fn finalized<O: ObjectType>(target: &O) -> Finalized {
+	let (tx, rx) = oneshot::channel();
+	let notify = Arc::new(Mutex::new(Some(tx)));
+	let target = unsafe {
+		gobject_ffi::g_object_weak_ref(
+			target.as_object_ref().to_glib_none().0,
+			Some(weak_notify),
+			Arc::into_raw(notify.clone()) as glib::ffi::gpointer,
+		);
+		target.unsafe_cast_ref::<glib::Object>().downgrade()
+	};
+	Finalized {
+		rx,
+		target,
+		notify,
+		_thread: PhantomData,
+	}
+}
+
+impl Finalized {
+	pub fn is_finalized(&self) -> bool {
+		self.rx.is_terminated()
+	}
+}
+
+impl Future for Finalized {
+	type Output = ();
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		// the sender is only dropped once the weak ref has been notified, or by our own drop
+		Pin::new(&mut self.get_mut().rx).poll(cx).map(|_| ())
+	}
+}
+
+impl FusedFuture for Finalized {
+	fn is_terminated(&self) -> bool {
+		self.rx.is_terminated()
+	}
+}
+
+impl Drop for Finalized {
+	fn drop(&mut self) {
+		let mut tx = self.notify.lock().unwrap_or_else(PoisonError::into_inner);
+		// the weak ref has already been notified, which g_object_run_dispose can do while the
+		// target is still alive
+		if tx.take().is_none() {
+			return
+		}
+		// otherwise the weak ref is still registered, unless the target is already being disposed,
+		// in which case it will be notified and release its reference on its own
+		if let Some(target) = self.target.upgrade() {
+			let data = Arc::as_ptr(&self.notify);
+			unsafe {
+				gobject_ffi::g_object_weak_unref(
+					target.as_object_ref().to_glib_none().0,
+					Some(weak_notify),
+					data as glib::ffi::gpointer,
+				);
+				drop(tx);
+				drop(Arc::from_raw(data));
+			}
+		}
+	}
+}
+
+impl fmt::Debug for Finalized {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Finalized").field("target", &self.target).finish()
+	}
+}
+
+/// Ends a stream once an object has been finalized.
+///
+/// Created by [take_until_finalized] or
+/// [SignalStream::take_until_finalized](crate::SignalStream::take_until_finalized).
+#[must_use]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+#[derive(Debug)]
+pub struct TakeUntilFinalized<St> {
+	stream: St,
+	finalized: Finalized,
+	done: bool,
+}
+
+/// Ends `stream` once `target` has been finalized, which doesn't need to be related to the stream.
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+pub fn take_until_finalized<St: Stream, O: ObjectType>(stream: St, target: &O) -> TakeUntilFinalized<St> {
+	TakeUntilFinalized {
+		stream,
+		finalized: finalized(target),
+		done: false,
+	}
+}
+
+impl<St> TakeUntilFinalized<St> {
+	pub fn into_inner(self) -> St {
+		self.stream
+	}
+}
+
+impl<St: Stream + Unpin> Stream for TakeUntilFinalized<St> {
+	type Item = St::Item;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+		if this.done || Pin::new(&mut this.finalized).poll(cx).is_ready() {
+			this.done = true;
+			return Poll::Ready(None)
+		}
+		let res = Pin::new(&mut this.stream).poll_next(cx);
+		if let Poll::Ready(None) = res {
+			this.done = true;
+		}
+		res
+	}
+}
+
+impl<St: Stream + Unpin> FusedStream for TakeUntilFinalized<St> {
+	fn is_terminated(&self) -> bool {
+		self.done
+	}
+}
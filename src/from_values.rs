@@ -1,6 +1,6 @@
 use {
 	glib::{
-		value::{FromValue, ValueTypeChecker, ValueTypeMismatchError, ValueTypeMismatchOrNoneError},
+		value::{FromValue, ToValue, ValueTypeChecker, ValueTypeMismatchError, ValueTypeMismatchOrNoneError},
 		StaticType, Type, Value,
 	},
 	std::error::Error,
@@ -16,6 +16,13 @@ pub trait FromValues<'a> {
 	fn static_types() -> Self::Types;
 }
 
+/// The inverse of [FromValues], used when re-emitting or chaining up with typed arguments.
+pub trait ToValues {
+	type Values: AsRef<[Value]>;
+
+	fn to_values(&self) -> Self::Values;
+}
+
 macro_rules! impl_signal_arguments {
 	($count:literal; ($($tx:ident),*)) => {
 		#[allow(non_snake_case)]
@@ -37,6 +44,18 @@ macro_rules! impl_signal_arguments {
 				[$($tx::static_type(),)*]
 			}
 		}
+
+		#[allow(non_snake_case)]
+		impl<$($tx,)*> ToValues for ($($tx, )*) where
+			$($tx: ToValue,)*
+		{
+			type Values = [Value; $count];
+
+			fn to_values(&self) -> Self::Values {
+				let ($($tx,)*) = self;
+				[$($tx.to_value(),)*]
+			}
+		}
 	};
 }
 
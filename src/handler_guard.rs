@@ -0,0 +1,51 @@
+use {
+	crate::ObjectSignalExt,
+	glib::{Cast, ObjectExt, ObjectType, SignalHandlerId, WeakRef},
+};
+
+/// Disconnects a signal handler when dropped.
+///
+/// Only holds onto its target weakly.
+#[must_use]
+#[derive(Debug)]
+pub struct HandlerGuard<O: ObjectType> {
+	target: WeakRef<O>,
+	handle: Option<SignalHandlerId>,
+}
+
+impl<O: ObjectType> HandlerGuard<O> {
+	pub fn new(target: &O, handle: SignalHandlerId) -> Self {
+		Self {
+			target: target.downgrade(),
+			handle: Some(handle),
+		}
+	}
+
+	pub fn target(&self) -> &WeakRef<O> {
+		&self.target
+	}
+
+	/// Whether the handler is still connected to a live target.
+	pub fn is_connected(&self) -> bool {
+		match (&self.handle, self.target.upgrade()) {
+			(Some(handle), Some(target)) => unsafe { target.unsafe_cast_ref::<glib::Object>() }.is_connected(handle),
+			_ => false,
+		}
+	}
+
+	pub fn disconnect(&mut self) {
+		if let Some(handle) = self.handle.take() {
+			if let Some(target) = self.target.upgrade() {
+				if unsafe { target.unsafe_cast_ref::<glib::Object>() }.is_connected(&handle) {
+					target.disconnect(handle);
+				}
+			}
+		}
+	}
+}
+
+impl<O: ObjectType> Drop for HandlerGuard<O> {
+	fn drop(&mut self) {
+		self.disconnect();
+	}
+}
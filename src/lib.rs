@@ -2,31 +2,80 @@
 #![cfg_attr(feature = "dox", feature(doc_notable_trait, doc_cfg))]
 
 #[cfg(feature = "futures")]
-pub use self::signal_stream::{ConnectEof, OnceFuture, SignalStream};
+pub use self::{
+	async_handler::AsyncPolicy,
+	emit_future::EmitFuture,
+	finalized::{finalized, take_until_finalized, Finalized, TakeUntilFinalized},
+	signal_sink::SignalSink,
+	signal_stream::{ConnectEof, MergedSignals, OnceError, OnceFuture, SignalStream},
+	signal_stream_set::SignalStreamSet,
+	stream_task::StreamTask,
+	timed::{Debounce, Sample, Throttle},
+};
 #[doc(hidden)]
 pub use glib; // for macro use
 pub use {
 	self::{
+		accumulator::{accumulate, Accumulator, AccumulatorFn, Collect, FirstWins, LastWins, Sum, TrueHandled},
+		block_guard::BlockGuard,
 		borrowed_object::BorrowedObject,
-		from_values::FromValues,
+		builder::TypedSignalBuilder,
+		class_handler::override_class_handler,
+		dynamic_signal::DynamicSignal,
+		from_values::{FromValues, ToValues},
+		handler_guard::HandlerGuard,
 		pointer::Pointer,
-		value_option::{PrimitiveValue, ToValueOption},
+		propagation::Propagation,
+		relay::relay,
+		runtime_signal::{RuntimeSignal, RuntimeSignalMarker},
+		signal_handles::{HandlesBlockGuard, SignalHandles},
+		value_option::{FromValueOption, PrimitiveValue, ToValueOption},
 	},
 	glib::SignalFlags,
 };
+#[cfg(feature = "futures")]
+use {glib::MainContext, std::future::Future};
 use {
 	glib::{
 		subclass::{signal::SignalBuilder, SignalId},
-		translate::{from_glib, IntoGlib, ToGlibPtr},
+		thread_guard::ThreadGuard,
+		translate::{from_glib, from_glib_borrow, Borrowed, FromGlibPtrBorrow, IntoGlib, ToGlibPtr},
 		value::FromValue,
-		BoolError, Closure, ObjectExt, ObjectType, Quark, SignalHandlerId, StaticType,
+		BoolError, Closure, ObjectExt, ObjectType, Quark, SignalHandlerId, StaticType, Type, Value,
 	},
-	std::{fmt::Debug, marker::PhantomData},
+	std::{fmt::Debug, marker::PhantomData, ptr},
 };
 
 #[cfg(feature = "futures")]
 mod signal_stream;
 
+#[cfg(feature = "futures")]
+mod async_handler;
+
+#[cfg(feature = "futures")]
+mod emit_future;
+
+#[cfg(feature = "futures")]
+mod context_handler;
+
+#[cfg(feature = "futures")]
+mod signal_sink;
+
+#[cfg(feature = "futures")]
+mod signal_stream_set;
+
+#[cfg(feature = "futures")]
+mod timed;
+
+#[cfg(feature = "futures")]
+mod finalized;
+
+#[cfg(feature = "futures")]
+mod stream_task;
+
+#[cfg(feature = "gio")]
+mod cancellable;
+
 mod borrowed_object;
 
 mod pointer;
@@ -35,15 +84,43 @@ mod value_option;
 
 mod from_values;
 
+mod class_handler;
+
+mod builder;
+
+mod accumulator;
+
+mod propagation;
+
+mod collect;
+
+mod emission_frame;
+
+mod block_guard;
+
+mod signal_handles;
+
+mod handler_guard;
+
+mod relay;
+
+mod dynamic_signal;
+
+mod runtime_signal;
+
 mod macros;
 
 pub trait Signal: Copy + Debug {
 	type Object: ObjectType;
 	type Arguments: for<'a> FromValues<'a> + 'static;
+	/// Must be an `Option` for types that start out as `NULL`, like a `String` or an object, which
+	/// is what an emission returns when no handler provides a value.
 	type Return: ToValueOption;
 
 	const NAME: &'static str;
 	const FLAGS: SignalFlags = SignalFlags::empty();
+	/// Used by [BuildableSignal] to combine handler return values, see [accumulate].
+	const ACCUMULATOR: Option<AccumulatorFn> = <Self::Return as ToValueOption>::ACCUMULATOR;
 
 	fn signal() -> SignalId {
 		SignalId::lookup(Self::NAME, <Self::Object as StaticType>::static_type()).expect(Self::NAME)
@@ -51,7 +128,8 @@ pub trait Signal: Copy + Debug {
 }
 
 pub trait DetailedSignal: Copy + Debug + Into<ConnectDetails<Self>> {
-	type Signal: Signal;
+	/// The [Signal] this refers to, or a marker for signals that are only known at runtime.
+	type Signal;
 	type Object: ObjectType;
 	type Arguments: for<'a> FromValues<'a> + 'static;
 	type Return: ToValueOption;
@@ -68,6 +146,13 @@ pub trait DetailedSignal: Copy + Debug + Into<ConnectDetails<Self>> {
 	fn create_detail() -> Quark {
 		Quark::from_str(Self::DETAIL.expect("detail string required"))
 	}
+
+	/// The signal and detail that `self` refers to.
+	///
+	/// Only signals that are looked up at runtime, like [RuntimeSignal], depend on `self`.
+	fn connect_details(self) -> ConnectDetails<Self> {
+		self.into()
+	}
 }
 
 impl<T: Signal> DetailedSignal for T {
@@ -80,30 +165,43 @@ impl<T: Signal> DetailedSignal for T {
 }
 
 pub trait BuildableSignal: Signal {
-	fn builder<F: FnOnce(SignalBuilder) -> glib::subclass::Signal>(f: F) -> glib::subclass::Signal;
+	/// A builder pre-populated with the signal's name, flags, and types.
+	///
+	/// Panics if the return type starts out as `NULL`, like a `String` or an object, unless it is
+	/// declared as an `Option`.
+	fn signal_builder() -> TypedSignalBuilder<Self>;
+
+	fn builder<F: FnOnce(SignalBuilder) -> glib::subclass::Signal>(f: F) -> glib::subclass::Signal {
+		f(Self::signal_builder().into_inner())
+	}
 }
 
 impl<T: Signal> BuildableSignal for T
 where
+	Self::Return: FromValueOption,
 	<Self::Return as ToValueOption>::Type: StaticType,
 {
-	fn builder<F: FnOnce(SignalBuilder) -> glib::subclass::Signal>(f: F) -> glib::subclass::Signal {
+	fn signal_builder() -> TypedSignalBuilder<Self> {
+		let return_type = <Self::Return as ToValueOption>::Type::static_type();
+		if let Err(e) = value_option::check_zero_value::<Self::Return>(return_type) {
+			panic!("{}: {}", Self::NAME, e)
+		}
 		let builder = glib::subclass::Signal::builder(Self::NAME)
 			.param_types(<Self::Arguments as FromValues>::static_types())
 			.return_type::<<Self::Return as ToValueOption>::Type>()
 			.flags(Self::FLAGS);
-		f(builder)
+		TypedSignalBuilder::new(builder)
 	}
 }
 
 pub trait BuildSignal: BuildableSignal {
 	fn build() -> glib::subclass::Signal {
-		Self::builder(|b| b.build())
+		Self::signal_builder().build()
 	}
 }
 
 #[cfg_attr(feature = "dox", doc(notable_trait))]
-pub trait Notifies<T: Signal>: ObjectType {}
+pub trait Notifies<T>: ObjectType {}
 
 #[derive(Copy, Clone, Debug)]
 pub struct ConnectDetails<S = ()> {
@@ -134,9 +232,45 @@ impl<S> ConnectDetails<S> {
 	pub fn detail(&self) -> Option<Quark> {
 		self.detail
 	}
+
+	pub(crate) fn match_mask(&self) -> glib::gobject_ffi::GSignalMatchType {
+		match self.detail {
+			Some(_) => glib::gobject_ffi::G_SIGNAL_MATCH_ID | glib::gobject_ffi::G_SIGNAL_MATCH_DETAIL,
+			None => glib::gobject_ffi::G_SIGNAL_MATCH_ID,
+		}
+	}
+
+	/// Emits the signal, returning the accumulated value.
+	fn emit<O: ObjectType>(&self, target: &O, args: &[Value]) -> Option<Value> {
+		match self.detail {
+			Some(detail) => target.emit_with_details_and_values(self.signal, detail, args),
+			None => target.emit_with_values(self.signal, args),
+		}
+	}
+
+	/// Like [emit](Self::emit), but hides its handlers from any enclosing
+	/// [emit_collect](ObjectSignalExt::emit_collect) or [emit_async](ObjectSignalExt::emit_async)
+	/// of the same signal on another instance.
+	fn emit_isolated<O: ObjectType>(&self, target: &O, args: &[Value]) -> Option<Value> {
+		let emit = || self.emit(target, args);
+		#[cfg(feature = "futures")]
+		let emit = || emit_future::isolate(self.signal, emit);
+		collect::isolate(self.signal, emit)
+	}
+
+	/// The value a handler returns when it can't be invoked, as the marshaller always expects one.
+	pub(crate) fn default_return(&self) -> Option<Value> {
+		match self.signal.query().return_type().type_() {
+			Type::UNIT => None,
+			ty => Some(Value::from_type(ty)),
+		}
+	}
 }
 
-impl<S: DetailedSignal> ConnectDetails<S> {
+impl<S: DetailedSignal> ConnectDetails<S>
+where
+	S::Signal: Signal,
+{
 	pub fn new() -> Self {
 		Self::with_after(false)
 	}
@@ -149,7 +283,9 @@ impl<S: DetailedSignal> ConnectDetails<S> {
 			_signal: PhantomData,
 		}
 	}
+}
 
+impl<S: DetailedSignal> ConnectDetails<S> {
 	pub fn set_detail(&mut self, detail: Quark) {
 		assert!(self.detail.is_none());
 		self.detail = Some(detail);
@@ -180,7 +316,10 @@ impl<S: DetailedSignal> From<ConnectDetails<S>> for ConnectDetails<()> {
 	}
 }
 
-impl<S: DetailedSignal> From<S> for ConnectDetails<S> {
+impl<S: DetailedSignal> From<S> for ConnectDetails<S>
+where
+	S::Signal: Signal,
+{
 	fn from(_: S) -> Self {
 		Self::new()
 	}
@@ -190,13 +329,157 @@ pub trait ObjectSignalExt: ObjectType {
 	unsafe fn handle_closure(&self, signal: &ConnectDetails, callback: &Closure) -> Result<SignalHandlerId, BoolError>;
 	fn remove_handle(&self, handle: SignalHandlerId);
 
+	/// Connects a handler that may be invoked from any thread the signal is emitted on.
 	fn handle<S, S_, C>(&self, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, S::Arguments) -> <S::Return as ToValueOption>::Type + Send + Sync,
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>;
+
+	/// Connects a handler that is only invoked on the thread it was connected from.
+	///
+	/// Emitting the signal from any other thread is a critical error that skips the handler, and
+	/// returns a zero-initialized value in its place. Like `ObjectExt::connect_local`, `callback`
+	/// is held in a [ThreadGuard], so disconnecting it or finalizing the object from another
+	/// thread panics rather than dropping it there.
+	fn handle_local<S, S_, C>(&self, signal: S_, callback: C) -> SignalHandlerId
 	where
 		C: Fn(&Self, S::Arguments) -> <S::Return as ToValueOption>::Type,
 		S: DetailedSignal,
 		S_: Into<ConnectDetails<S>>,
 		Self: Notifies<S::Signal>;
 
+	/// Connects a handler that also receives `bound`, without keeping it alive.
+	///
+	/// The handler is disconnected automatically once `bound` is disposed, like
+	/// `g_signal_connect_object`, and `bound` is kept alive while the handler runs. It is only
+	/// invoked on the thread it was connected from, like
+	/// [handle_local](ObjectSignalExt::handle_local).
+	fn handle_bound<S, S_, B, C>(&self, bound: &B, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, &B, S::Arguments) -> <S::Return as ToValueOption>::Type,
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		B: ObjectType + FromGlibPtrBorrow<*mut B::GlibType>,
+		Self: Notifies<S::Signal>;
+
+	/// Connects a handler that is disconnected once `cancellable` is triggered.
+	///
+	/// The handler is disconnected immediately if `cancellable` has already been cancelled.
+	/// Cancelling from another thread defers the disconnect to the thread-default main context
+	/// this was called from.
+	#[cfg(feature = "gio")]
+	#[cfg_attr(feature = "dox", doc(cfg(feature = "gio")))]
+	fn handle_until<S, S_, C>(&self, cancellable: &gio::Cancellable, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, S::Arguments) -> <S::Return as ToValueOption>::Type,
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>;
+
+	/// Connects a handler to a signal that is only known at runtime.
+	///
+	/// The handler is only invoked on the thread it was connected from, like
+	/// [handle_local](ObjectSignalExt::handle_local). Values it returns that don't match the
+	/// signal's return type are replaced with a default value.
+	fn handle_dynamic<C>(&self, signal: &DynamicSignal, callback: C) -> Result<SignalHandlerId, BoolError>
+	where
+		C: Fn(&Self, &[Value]) -> Option<Value> + 'static;
+
+	/// Emits a signal that is only known at runtime, after checking `args` against its parameter
+	/// types.
+	fn emit_dynamic(&self, signal: &DynamicSignal, args: &[Value]) -> Result<Option<Value>, BoolError>;
+
+	/// Whether `handle` is still connected to this object.
+	fn is_connected(&self, handle: &SignalHandlerId) -> bool;
+
+	/// The number of unblocked handlers connected to the signal.
+	///
+	/// Only handlers connected with the same detail are counted if `signal` specifies one.
+	///
+	/// GLib has no way to query handlers without modifying them, so each one is briefly blocked
+	/// while counting. An emission from another thread during the count will skip them.
+	fn handler_count<S, S_>(&self, signal: S_) -> usize
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>;
+
+	/// Disconnects every handler connected to the signal, returning how many were removed.
+	///
+	/// Only handlers connected with the same detail are removed if `signal` specifies one.
+	fn disconnect_all<S, S_>(&self, signal: S_) -> usize
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>;
+
+	/// Blocks `handle` until the returned guard is dropped.
+	fn block_handler(&self, handle: &SignalHandlerId) -> BlockGuard<'_, Self>;
+
+	/// Blocks every handler connected to the signal until the returned guard is dropped.
+	///
+	/// Handlers connected while the guard is held are not blocked.
+	fn block_handlers<S, S_>(&self, signal: S_) -> BlockGuard<'_, Self>
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>;
+
+	/// Whether any unblocked handlers are connected to the signal, or a subclass has overridden its
+	/// class handler.
+	fn has_handler_pending<S, S_>(&self, signal: S_) -> bool
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>;
+
+	/// Emits the signal only if doing so would invoke a handler, constructing its arguments on
+	/// demand.
+	///
+	/// Emission is only skipped for signals that were built by [TypedSignalBuilder::build] without
+	/// a class handler, and have no handlers connected. Emission hooks cannot be detected, so
+	/// such signals should not rely on them.
+	fn emit_lazy<S, S_, F>(&self, signal: S_, args: F) -> Option<<S::Return as ToValueOption>::Type>
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		F: FnOnce() -> S::Arguments,
+		S::Arguments: ToValues,
+		S::Return: FromValueOption,
+		Self: Notifies<S::Signal>;
+
+	/// Emits the signal, returning the accumulated return value of its handlers.
+	fn emit_signal<S, S_>(&self, signal: S_, args: S::Arguments) -> <S::Return as ToValueOption>::Type
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		S::Arguments: ToValues,
+		S::Return: FromValueOption,
+		Self: Notifies<S::Signal>;
+
+	/// Emits the signal, returning the value of each handler (including the class handler) in the
+	/// order they were invoked.
+	///
+	/// Individual return values can only be observed through the accumulator installed by
+	/// [TypedSignalBuilder], so this fails if handlers are connected to a signal that was registered
+	/// some other way, such as those defined in C. Signals returning `()` have no accumulator and
+	/// cannot be collected either. Nested emissions of the same signal from within a handler are
+	/// kept separate when they go through this crate, but those made some other way, such as from
+	/// C or `ObjectExt::emit_by_name`, are collected along with the outer emission.
+	fn emit_collect<S, S_>(
+		&self,
+		signal: S_,
+		args: S::Arguments,
+	) -> Result<Vec<<S::Return as ToValueOption>::Type>, BoolError>
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		S::Arguments: ToValues,
+		S::Return: FromValueOption,
+		Self: Notifies<S::Signal>;
+
 	#[cfg(feature = "futures")]
 	fn signal_stream<S, S_>(&self, signal: S_) -> SignalStream<Self, S::Arguments>
 	where
@@ -204,6 +487,96 @@ pub trait ObjectSignalExt: ObjectType {
 		S_: Into<ConnectDetails<S>>,
 		Self: Notifies<S::Signal>,
 		<S::Return as ToValueOption>::Type: Default;
+
+	/// A stream of the arguments of a signal that is only known at runtime.
+	#[cfg(feature = "futures")]
+	fn signal_stream_dynamic(&self, signal: &DynamicSignal) -> Result<SignalStream<Self, Vec<Value>>, BoolError> {
+		SignalStream::connect_dynamic(self, signal)
+	}
+
+	/// A sink that emits the signal for every item sent to it.
+	#[cfg(feature = "futures")]
+	fn signal_sink<S, S_>(&self, signal: S_) -> SignalSink<Self, S>
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		SignalSink::new(self, signal)
+	}
+
+	/// Connects a handler that invokes `callback` on `context` instead of the emitting thread.
+	///
+	/// Emissions are delivered in order, and the handler is disconnected once the returned guard
+	/// is dropped. Emissions that have not been delivered by then are discarded.
+	#[cfg(feature = "futures")]
+	fn handle_on_context<S, S_, C>(&self, context: &MainContext, signal: S_, callback: C) -> HandlerGuard<Self>
+	where
+		C: Fn(S::Arguments) + Send + 'static,
+		S: DetailedSignal,
+		S::Arguments: Send,
+		S::Return: ToValueOption<Type = ()>,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>;
+
+	/// Connects a handler whose future is awaited by [emit_async](ObjectSignalExt::emit_async).
+	///
+	/// The handler itself returns the default value to the emission, and its future is dropped
+	/// without being polled if the signal was emitted some other way.
+	#[cfg(feature = "futures")]
+	fn handle_future<S, S_, C, F>(&self, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, S::Arguments) -> F + 'static,
+		F: Future<Output = <S::Return as ToValueOption>::Type> + 'static,
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+		<S::Return as ToValueOption>::Type: Default + 'static;
+
+	/// Emits the signal, returning a future of the results of its
+	/// [handle_future](ObjectSignalExt::handle_future) handlers in the order they were invoked.
+	///
+	/// The signal is emitted immediately, rather than when the returned future is first polled.
+	/// Like with [emit_collect](ObjectSignalExt::emit_collect), only nested emissions of the same
+	/// signal that don't go through this crate contribute their futures to the outer emission.
+	#[cfg(feature = "futures")]
+	fn emit_async<S, S_>(&self, signal: S_, args: S::Arguments) -> EmitFuture<<S::Return as ToValueOption>::Type>
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		S::Arguments: ToValues,
+		S::Return: FromValueOption,
+		Self: Notifies<S::Signal>,
+		<S::Return as ToValueOption>::Type: 'static;
+
+	/// Connects a handler that spawns the returned future onto the thread-default main context.
+	///
+	/// Futures from overlapping emissions run concurrently, and any still running are aborted
+	/// once the handler is disconnected.
+	#[cfg(feature = "futures")]
+	fn handle_async<S, S_, C, F>(&self, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, S::Arguments) -> F + 'static,
+		F: Future<Output = ()> + 'static,
+		S: DetailedSignal,
+		S::Return: ToValueOption<Type = ()>,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		self.handle_async_with(AsyncPolicy::default(), signal, callback)
+	}
+
+	/// Like [handle_async](ObjectSignalExt::handle_async), with `policy` deciding how the
+	/// futures of overlapping emissions are run.
+	#[cfg(feature = "futures")]
+	fn handle_async_with<S, S_, C, F>(&self, policy: AsyncPolicy, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, S::Arguments) -> F + 'static,
+		F: Future<Output = ()> + 'static,
+		S: DetailedSignal,
+		S::Return: ToValueOption<Type = ()>,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>;
 }
 
 impl<O: ObjectType> ObjectSignalExt for O
@@ -230,7 +603,7 @@ where
 
 	fn handle<S, S_, C>(&self, signal: S_, callback: C) -> SignalHandlerId
 	where
-		C: Fn(&Self, S::Arguments) -> <S::Return as ToValueOption>::Type,
+		C: Fn(&Self, S::Arguments) -> <S::Return as ToValueOption>::Type + Send + Sync,
 		S: DetailedSignal,
 		S_: Into<ConnectDetails<S>>,
 		Self: Notifies<S::Signal>,
@@ -247,10 +620,268 @@ where
 		}
 	}
 
+	fn handle_local<S, S_, C>(&self, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, S::Arguments) -> <S::Return as ToValueOption>::Type,
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		let signal = signal.into();
+		let default_return = signal.default_return();
+		let callback = ThreadGuard::new(callback);
+		unsafe {
+			let callback = Closure::new_unsafe(move |values| {
+				if !callback.is_owner() {
+					glib::g_critical!("glib-signal", "{:?} handler invoked from a foreign thread", signal);
+					return default_return.clone()
+				}
+				let (this, args) = values.split_first().unwrap();
+				let this: BorrowedObject<Self> = this.get().unwrap();
+				let args = S::Arguments::from_values(args).unwrap();
+				(callback.get_ref())(&this, args).into().to_value_option()
+			});
+			self.handle_closure(&signal.normalize(), &callback).unwrap()
+		}
+	}
+
+	fn handle_bound<S, S_, B, C>(&self, bound: &B, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, &B, S::Arguments) -> <S::Return as ToValueOption>::Type,
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		B: ObjectType + FromGlibPtrBorrow<*mut B::GlibType>,
+		Self: Notifies<S::Signal>,
+	{
+		let signal = signal.into();
+		// the watched closure is invalidated once `bound` is disposed, and `bound` is referenced
+		// for the duration of each invocation
+		let bound_ptr = bound.as_ptr();
+		let default_return = signal.default_return();
+		let callback = ThreadGuard::new(callback);
+		unsafe {
+			let callback = Closure::new_unsafe(move |values| {
+				if !callback.is_owner() {
+					glib::g_critical!("glib-signal", "{:?} handler invoked from a foreign thread", signal);
+					return default_return.clone()
+				}
+				let (this, args) = values.split_first().unwrap();
+				let this: BorrowedObject<Self> = this.get().unwrap();
+				let bound: Borrowed<B> = from_glib_borrow(bound_ptr);
+				let args = S::Arguments::from_values(args).unwrap();
+				(callback.get_ref())(&this, &bound, args).into().to_value_option()
+			});
+			bound.watch_closure(&callback);
+			self.handle_closure(&signal.normalize(), &callback).unwrap()
+		}
+	}
+
+	#[cfg(feature = "gio")]
+	fn handle_until<S, S_, C>(&self, cancellable: &gio::Cancellable, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, S::Arguments) -> <S::Return as ToValueOption>::Type,
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		let (handler, cancel) = cancellable::UntilCancelled::new(callback);
+		let handle = self.handle_local(signal, move |this, args| handler.call(this, args));
+		*cancel.borrow_mut() = Some(cancellable::CancelHandle::disconnect_on_cancel(
+			self,
+			&handle,
+			cancellable,
+		));
+		handle
+	}
+
 	fn remove_handle(&self, handle: SignalHandlerId) {
 		self.disconnect(handle)
 	}
 
+	fn handle_dynamic<C>(&self, signal: &DynamicSignal, callback: C) -> Result<SignalHandlerId, BoolError>
+	where
+		C: Fn(&Self, &[Value]) -> Option<Value> + 'static,
+	{
+		signal.check_object(self.type_())?;
+		let details = *signal.details();
+		let signal = signal.clone();
+		let callback = ThreadGuard::new(callback);
+		unsafe {
+			let callback = Closure::new_unsafe(move |values| {
+				if !callback.is_owner() {
+					glib::g_critical!("glib-signal", "{} handler invoked from a foreign thread", signal.name());
+					return signal.default_return()
+				}
+				let (this, args) = values.split_first().unwrap();
+				let this: BorrowedObject<Self> = this.get().unwrap();
+				if let Err(e) = signal.check_args(args) {
+					glib::g_critical!("glib-signal", "{}", e);
+					return signal.default_return()
+				}
+				let res = (callback.get_ref())(&this, args);
+				match signal.check_return(res.as_ref()) {
+					Ok(()) => res,
+					Err(e) => {
+						glib::g_critical!("glib-signal", "{}", e);
+						signal.default_return()
+					},
+				}
+			});
+			self.handle_closure(&details, &callback)
+		}
+	}
+
+	fn emit_dynamic(&self, signal: &DynamicSignal, args: &[Value]) -> Result<Option<Value>, BoolError> {
+		signal.check_object(self.type_())?;
+		signal.check_args(args)?;
+		let res = signal.details().emit_isolated(self, args);
+		signal.check_return(res.as_ref())?;
+		Ok(res)
+	}
+
+	fn is_connected(&self, handle: &SignalHandlerId) -> bool {
+		unsafe {
+			from_glib(glib::gobject_ffi::g_signal_handler_is_connected(
+				self.as_object_ref().to_glib_none().0,
+				handle.as_raw(),
+			))
+		}
+	}
+
+	fn handler_count<S, S_>(&self, signal: S_) -> usize
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		// glib can only enumerate handlers by blocking them one at a time
+		BlockGuard::block_matched(self, &signal.into().normalize()).blocked()
+	}
+
+	fn disconnect_all<S, S_>(&self, signal: S_) -> usize
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		let signal = signal.into();
+		let instance = self.as_object_ref().to_glib_none().0;
+		let mut count = 0;
+		// g_signal_handlers_disconnect_matched ignores a mask without a closure, func or data
+		loop {
+			let handle = unsafe {
+				glib::gobject_ffi::g_signal_handler_find(
+					instance,
+					signal.match_mask(),
+					signal.signal().into_glib(),
+					signal.detail().map(|q| q.into_glib()).unwrap_or(0),
+					ptr::null_mut(),
+					ptr::null_mut(),
+					ptr::null_mut(),
+				)
+			};
+			match handle {
+				0 => break count,
+				handle => unsafe {
+					glib::gobject_ffi::g_signal_handler_disconnect(instance, handle);
+					count += 1;
+				},
+			}
+		}
+	}
+
+	fn block_handler(&self, handle: &SignalHandlerId) -> BlockGuard<'_, Self> {
+		BlockGuard::block_handler(self, handle)
+	}
+
+	fn block_handlers<S, S_>(&self, signal: S_) -> BlockGuard<'_, Self>
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		let signal = signal.into();
+		BlockGuard::block_matched(self, &signal.normalize())
+	}
+
+	fn has_handler_pending<S, S_>(&self, signal: S_) -> bool
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		let signal = signal.into();
+		unsafe {
+			from_glib(glib::gobject_ffi::g_signal_has_handler_pending(
+				self.as_object_ref().to_glib_none().0,
+				signal.signal().into_glib(),
+				signal.detail().map(|q| q.into_glib()).unwrap_or(0),
+				false.into_glib(),
+			))
+		}
+	}
+
+	fn emit_lazy<S, S_, F>(&self, signal: S_, args: F) -> Option<<S::Return as ToValueOption>::Type>
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		F: FnOnce() -> S::Arguments,
+		S::Arguments: ToValues,
+		S::Return: FromValueOption,
+		Self: Notifies<S::Signal>,
+	{
+		let signal = signal.into();
+		match self.has_handler_pending::<S, _>(signal) || !class_handler::lacks_class_handler(signal.signal()) {
+			true => Some(self.emit_signal::<S, _>(signal, args())),
+			false => None,
+		}
+	}
+
+	fn emit_signal<S, S_>(&self, signal: S_, args: S::Arguments) -> <S::Return as ToValueOption>::Type
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		S::Arguments: ToValues,
+		S::Return: FromValueOption,
+		Self: Notifies<S::Signal>,
+	{
+		let signal = signal.into();
+		let res = signal.emit_isolated(self, args.to_values().as_ref());
+		S::Return::from_value_option(res.as_ref()).unwrap()
+	}
+
+	fn emit_collect<S, S_>(
+		&self,
+		signal: S_,
+		args: S::Arguments,
+	) -> Result<Vec<<S::Return as ToValueOption>::Type>, BoolError>
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		S::Arguments: ToValues,
+		S::Return: FromValueOption,
+		Self: Notifies<S::Signal>,
+	{
+		let signal = signal.into();
+		let pending = self.has_handler_pending::<S, _>(signal);
+		let values = collect::collect(signal.signal(), || {
+			signal.emit(self, args.to_values().as_ref());
+		});
+		if values.is_empty() && pending {
+			return Err(glib::bool_error!(
+				"signal {:?} of type {:?} does not support collecting return values",
+				signal,
+				Self::static_type()
+			))
+		}
+		Ok(
+			values
+				.iter()
+				.map(|value| S::Return::from_value_option(Some(value)).unwrap())
+				.collect(),
+		)
+	}
+
 	#[cfg(feature = "futures")]
 	fn signal_stream<S, S_>(&self, signal: S_) -> SignalStream<Self, S::Arguments>
 	where
@@ -262,4 +893,67 @@ where
 		let signal = signal.into();
 		SignalStream::connect(self, signal, |_, _| Default::default())
 	}
+
+	#[cfg(feature = "futures")]
+	fn handle_on_context<S, S_, C>(&self, context: &MainContext, signal: S_, callback: C) -> HandlerGuard<Self>
+	where
+		C: Fn(S::Arguments) + Send + 'static,
+		S: DetailedSignal,
+		S::Arguments: Send,
+		S::Return: ToValueOption<Type = ()>,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		let forwarder = context_handler::Forwarder::spawn(context, callback);
+		let handle = self.handle(signal, move |_, args| forwarder.send(args));
+		HandlerGuard::new(self, handle)
+	}
+
+	#[cfg(feature = "futures")]
+	fn handle_future<S, S_, C, F>(&self, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, S::Arguments) -> F + 'static,
+		F: Future<Output = <S::Return as ToValueOption>::Type> + 'static,
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+		<S::Return as ToValueOption>::Type: Default + 'static,
+	{
+		let signal = signal.into();
+		let id = signal.signal();
+		self.handle_local::<S, _, _>(signal, move |this, args| {
+			emit_future::submit(id, Box::pin(callback(this, args)));
+			Default::default()
+		})
+	}
+
+	#[cfg(feature = "futures")]
+	fn emit_async<S, S_>(&self, signal: S_, args: S::Arguments) -> EmitFuture<<S::Return as ToValueOption>::Type>
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		S::Arguments: ToValues,
+		S::Return: FromValueOption,
+		Self: Notifies<S::Signal>,
+		<S::Return as ToValueOption>::Type: 'static,
+	{
+		let signal = signal.into();
+		emit_future::collect(signal.signal(), || {
+			signal.emit(self, args.to_values().as_ref());
+		})
+	}
+
+	#[cfg(feature = "futures")]
+	fn handle_async_with<S, S_, C, F>(&self, policy: AsyncPolicy, signal: S_, callback: C) -> SignalHandlerId
+	where
+		C: Fn(&Self, S::Arguments) -> F + 'static,
+		F: Future<Output = ()> + 'static,
+		S: DetailedSignal,
+		S::Return: ToValueOption<Type = ()>,
+		S_: Into<ConnectDetails<S>>,
+		Self: Notifies<S::Signal>,
+	{
+		let handler = async_handler::AsyncHandler::new(policy);
+		self.handle_local(signal, move |this, args| handler.spawn(Box::pin(callback(this, args))))
+	}
 }
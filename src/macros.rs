@@ -10,6 +10,7 @@
 ///     //impl {const SIGNAL_NAME}; // provide a convenient accessor for the default signal handler
 ///     impl BuildSignal; // provide a default impl to facilitate GObject type construction
 ///     FLAGS = SignalFlags::NO_RECURSE; // optionally specify flags for the signal when building
+///     //ACCUMULATOR = glib_signal::Sum; // optionally combine handler return values when building
 ///     fn(&self, String) // finally, specify the callback handler signature (with optional return type)
 /// } }
 /// ```
@@ -20,11 +21,18 @@
 /// - [Signal](crate::Signal) for `SignalType`
 /// - [BuildSignal](crate::BuildSignal) for `SignalType`, for use with
 ///   [glib::ObjectImpl](glib::subclass::object::ObjectImpl::signals) (opt-in)
+///
+/// ## Accumulators
+///
+/// `ACCUMULATOR` accepts any [Accumulator](crate::Accumulator) that implements `Default`, such as
+/// [FirstWins](crate::FirstWins), [LastWins](crate::LastWins), [Sum](crate::Sum),
+/// [TrueHandled](crate::TrueHandled), or [Collect](crate::Collect).
 #[macro_export]
 macro_rules! impl_signal {
 	(impl Notifies<$signal_str:literal as $signal:path> for $obj:path {
 		$(impl $imp:tt;)*
 		$(FLAGS = $flags:expr;)*
+		$(ACCUMULATOR = $accumulator:ty;)*
 		fn $($handler:tt)*
 	}) => {
 		impl $crate::Signal for $signal {
@@ -33,6 +41,9 @@ macro_rules! impl_signal {
 			$(
 				$crate::_impl_signal_private! { @line FLAGS $flags }
 			)*
+			$(
+				$crate::_impl_signal_private! { @line ACCUMULATOR $accumulator }
+			)*
 
 			const NAME: &'static str = $signal_str;
 		}
@@ -62,6 +73,9 @@ macro_rules! _impl_signal_private {
 	(@line FLAGS $flags:expr) => {
 		const FLAGS: $crate::glib::SignalFlags = $flags;
 	};
+	(@line ACCUMULATOR $accumulator:ty) => {
+		const ACCUMULATOR: Option<$crate::AccumulatorFn> = Some($crate::accumulate::<Self, $accumulator>);
+	};
 	(@impl ($signal:path) ($obj:path) BuildSignal) => {
 		impl $crate::BuildSignal for $signal { }
 	};
@@ -92,3 +106,61 @@ macro_rules! def_signal {
 		}
 	};
 }
+
+/// Defines an enum with a variant for each of several signals of one object, which can be awaited
+/// together as a single stream.
+///
+/// ## Syntax
+///
+/// ```ignore
+/// glib_signal::signal_enum! {
+///     #[derive(Debug)]
+///     pub enum TaskEvent for Task {
+///         Finished(TaskFinished),
+///         Error(TaskError),
+///         Cancelled(TaskCancelled),
+///     }
+/// }
+/// ```
+///
+/// ## Defines
+///
+/// - one variant for each signal, holding its [Arguments](crate::DetailedSignal::Arguments)
+/// - `TaskEvent::stream(&Task)`, a [SignalStream](crate::SignalStream) of every emission in the
+///   order they happened, with its handlers disconnected together
+/// - `TaskEvent::once(&Task)`, an [OnceFuture](crate::OnceFuture) of whichever signal is emitted
+///   first
+#[cfg(feature = "futures")]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+#[macro_export]
+macro_rules! signal_enum {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $name:ident for $obj:ty {
+			$($variant:ident($signal:ty)),* $(,)?
+		}
+	) => {
+		$(
+			#[$meta]
+		)*
+		$vis enum $name {
+			$(
+				$variant(<$signal as $crate::DetailedSignal>::Arguments),
+			)*
+		}
+
+		impl $name {
+			$vis fn stream(target: &$obj) -> $crate::SignalStream<$obj, Self> {
+				$crate::SignalStream::connect_merged(target, |signals| {
+					$(
+						signals.signal::<$signal, _>($crate::ConnectDetails::<$signal>::new(), Self::$variant);
+					)*
+				})
+			}
+
+			$vis fn once(target: &$obj) -> $crate::OnceFuture<$obj, Self> {
+				Self::stream(target).once()
+			}
+		}
+	};
+}
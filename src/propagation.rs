@@ -0,0 +1,94 @@
+use {
+	crate::{accumulator::accumulate_value, AccumulatorFn, FromValueOption, ToValueOption, TrueHandled},
+	glib::{
+		value::{FromValue, GenericValueTypeChecker, ToValue, ValueTypeMismatchError, ValueTypeMismatchOrNoneError},
+		StaticType, Type, Value,
+	},
+};
+
+/// The return value of event-style signals, represented as a `bool` where `true` stops the
+/// emission.
+///
+/// Signals returning `Propagation` default to the [TrueHandled] accumulator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Propagation {
+	/// Stop other handlers from being invoked.
+	Stop,
+	/// Continue invoking the remaining handlers.
+	Proceed,
+}
+
+impl Propagation {
+	pub fn is_stop(&self) -> bool {
+		matches!(self, Self::Stop)
+	}
+
+	pub fn is_proceed(&self) -> bool {
+		matches!(self, Self::Proceed)
+	}
+}
+
+impl Default for Propagation {
+	fn default() -> Self {
+		Self::Proceed
+	}
+}
+
+impl From<bool> for Propagation {
+	fn from(stop: bool) -> Self {
+		match stop {
+			true => Self::Stop,
+			false => Self::Proceed,
+		}
+	}
+}
+
+impl From<Propagation> for bool {
+	fn from(propagation: Propagation) -> Self {
+		propagation.is_stop()
+	}
+}
+
+impl From<glib::Propagation> for Propagation {
+	fn from(propagation: glib::Propagation) -> Self {
+		bool::from(propagation).into()
+	}
+}
+
+impl From<Propagation> for glib::Propagation {
+	fn from(propagation: Propagation) -> Self {
+		bool::from(propagation).into()
+	}
+}
+
+impl ToValueOption for Propagation {
+	type Type = Self;
+
+	const ACCUMULATOR: Option<AccumulatorFn> = Some(accumulate_value::<Self, TrueHandled>);
+
+	fn to_value_option(self) -> Option<Value> {
+		Some(bool::from(self).to_value())
+	}
+}
+
+impl FromValueOption for Propagation {
+	fn from_value_option(
+		value: Option<&Value>,
+	) -> Result<Self::Type, ValueTypeMismatchOrNoneError<ValueTypeMismatchError>> {
+		<bool as FromValueOption>::from_value_option(value).map(Into::into)
+	}
+}
+
+unsafe impl<'a> FromValue<'a> for Propagation {
+	type Checker = GenericValueTypeChecker<Self>;
+
+	unsafe fn from_value(value: &'a Value) -> Self {
+		bool::from_value(value).into()
+	}
+}
+
+impl StaticType for Propagation {
+	fn static_type() -> Type {
+		bool::static_type()
+	}
+}
@@ -0,0 +1,34 @@
+use {
+	crate::{ConnectDetails, DetailedSignal, FromValueOption, Notifies, ObjectSignalExt, ToValueOption, ToValues},
+	glib::{translate::FromGlibPtrBorrow, SignalHandlerId},
+};
+
+/// Re-emits `source_signal` of `source` as `target_signal` on `target`.
+///
+/// `map` converts the arguments of each emission, and the value returned by `target` is
+/// propagated back to the emitter of `source_signal`. The relay is disconnected once either
+/// object is finalized, without keeping `target` alive.
+pub fn relay<S1, S1_, S2, S2_, O, T, F>(
+	source: &O,
+	source_signal: S1_,
+	target: &T,
+	target_signal: S2_,
+	map: F,
+) -> SignalHandlerId
+where
+	S1: DetailedSignal,
+	S1_: Into<ConnectDetails<S1>>,
+	S2: DetailedSignal,
+	S2_: Into<ConnectDetails<S2>>,
+	S2::Arguments: ToValues,
+	S2::Return: FromValueOption,
+	<S2::Return as ToValueOption>::Type: Into<<S1::Return as ToValueOption>::Type>,
+	O: ObjectSignalExt + Notifies<S1::Signal>,
+	T: ObjectSignalExt + Notifies<S2::Signal> + FromGlibPtrBorrow<*mut T::GlibType>,
+	F: Fn(S1::Arguments) -> S2::Arguments + 'static,
+{
+	let target_signal = target_signal.into();
+	source.handle_bound(target, source_signal, move |_, target, args| {
+		target.emit_signal::<S2, _>(target_signal, map(args)).into()
+	})
+}
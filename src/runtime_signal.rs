@@ -0,0 +1,129 @@
+use {
+	crate::{
+		value_option::check_zero_value, ConnectDetails, DetailedSignal, DynamicSignal, FromValueOption, FromValues,
+		Notifies, ToValueOption,
+	},
+	glib::{subclass::SignalId, BoolError, IsA, ObjectType, Quark, StaticType, Type},
+	std::{fmt, marker::PhantomData},
+};
+
+type Types<O, A, R> = PhantomData<fn() -> (O, A, R)>;
+
+/// A typed signal of `O` looked up at runtime.
+///
+/// Its types are checked once by [lookup](RuntimeSignal::lookup), after which it can be used
+/// anywhere a static [DetailedSignal] can.
+pub struct RuntimeSignal<O, A, R> {
+	signal: SignalId,
+	detail: Option<Quark>,
+	_types: Types<O, A, R>,
+}
+
+impl<O, A, R> RuntimeSignal<O, A, R>
+where
+	O: ObjectType,
+	A: for<'a> FromValues<'a> + 'static,
+	R: FromValueOption,
+	R::Type: StaticType,
+{
+	/// Looks up a signal of `O` by name, which may include a `::detail`.
+	pub fn lookup(name: &str) -> Result<Self, BoolError> {
+		Self::with_dynamic(&DynamicSignal::lookup(O::static_type(), name)?)
+	}
+
+	/// Fails unless `signal` belongs to `O` and its types match `A` and `R`.
+	///
+	/// Return types that start out as `NULL` must be declared as an `Option`.
+	pub fn with_dynamic(signal: &DynamicSignal) -> Result<Self, BoolError> {
+		signal.check_object(O::static_type())?;
+		let param_types: Vec<Type> = <A as FromValues>::static_types().into_iter().collect();
+		if param_types != signal.param_types() {
+			return Err(glib::bool_error!(
+				"signal {:?} has parameters {:?}, not {:?}",
+				signal.name(),
+				signal.param_types(),
+				param_types
+			))
+		}
+		if R::Type::static_type() != signal.return_type() {
+			return Err(glib::bool_error!(
+				"signal {:?} returns {:?}, not {:?}",
+				signal.name(),
+				signal.return_type(),
+				R::Type::static_type()
+			))
+		}
+		check_zero_value::<R>(signal.return_type())?;
+		let details = signal.details();
+		Ok(Self {
+			signal: details.signal(),
+			detail: details.detail(),
+			_types: PhantomData,
+		})
+	}
+}
+
+impl<O, A, R> DetailedSignal for RuntimeSignal<O, A, R>
+where
+	O: ObjectType,
+	A: for<'a> FromValues<'a> + 'static,
+	R: ToValueOption,
+{
+	type Arguments = A;
+	type Object = O;
+	type Return = R;
+	type Signal = RuntimeSignalMarker<O, A, R>;
+
+	const DETAIL: Option<&'static str> = None;
+}
+
+impl<O, A, R> From<RuntimeSignal<O, A, R>> for ConnectDetails<RuntimeSignal<O, A, R>> {
+	fn from(signal: RuntimeSignal<O, A, R>) -> Self {
+		unsafe { ConnectDetails::with_parts(signal.signal, signal.detail, false) }
+	}
+}
+
+impl<O, A, R> Clone for RuntimeSignal<O, A, R> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<O, A, R> Copy for RuntimeSignal<O, A, R> {}
+
+impl<O, A, R> fmt::Debug for RuntimeSignal<O, A, R> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("RuntimeSignal")
+			.field("signal", &self.signal)
+			.field("detail", &self.detail)
+			.finish()
+	}
+}
+
+/// The [DetailedSignal::Signal] of every [RuntimeSignal], which has no static signal id.
+///
+/// Objects that are an `O` [Notifies] it.
+pub struct RuntimeSignalMarker<O, A, R> {
+	_types: Types<O, A, R>,
+}
+
+impl<T, O, A, R> Notifies<RuntimeSignalMarker<O, A, R>> for T
+where
+	T: IsA<O>,
+	O: ObjectType,
+{
+}
+
+impl<O, A, R> Clone for RuntimeSignalMarker<O, A, R> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<O, A, R> Copy for RuntimeSignalMarker<O, A, R> {}
+
+impl<O, A, R> fmt::Debug for RuntimeSignalMarker<O, A, R> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("RuntimeSignalMarker")
+	}
+}
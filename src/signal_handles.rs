@@ -0,0 +1,127 @@
+use {
+	crate::ObjectSignalExt,
+	glib::{
+		gobject_ffi,
+		translate::{from_glib, from_glib_none, ToGlibPtr},
+		ObjectExt, ObjectType, SignalHandlerId, WeakRef,
+	},
+	std::{cell::RefCell, fmt, mem, os::raw::c_ulong},
+};
+
+/// Signal handlers connected to other objects, disconnected when dropped.
+///
+/// Handlers may belong to any signal of any object, and their targets are only held weakly. This
+/// is meant to live in a subclass' `imp` struct, where
+/// [disconnect_all](SignalHandles::disconnect_all) can be called from `ObjectImpl::dispose`:
+///
+/// ```ignore
+/// fn dispose(&self) {
+///     self.handles.disconnect_all();
+/// }
+/// ```
+#[derive(Default)]
+pub struct SignalHandles {
+	handles: RefCell<Vec<(WeakRef<glib::Object>, SignalHandlerId)>>,
+}
+
+impl SignalHandles {
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Tracks `handle`, which must have been connected to `target`.
+	pub fn push<O: ObjectType>(&self, target: &O, handle: SignalHandlerId) {
+		let target = downgrade(target);
+		self.handles.borrow_mut().push((target, handle))
+	}
+
+	pub fn len(&self) -> usize {
+		self.handles.borrow().len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.handles.borrow().is_empty()
+	}
+
+	/// Disconnects every handler that is still connected, and stops tracking all of them.
+	pub fn disconnect_all(&self) {
+		let handles = mem::take(&mut *self.handles.borrow_mut());
+		for (target, handle) in handles {
+			if let Some(target) = target.upgrade() {
+				if target.is_connected(&handle) {
+					target.disconnect(handle);
+				}
+			}
+		}
+	}
+
+	/// Blocks every tracked handler until the returned guard is dropped.
+	///
+	/// Handlers pushed while the guard is held are not blocked.
+	pub fn block_all(&self) -> HandlesBlockGuard {
+		let handlers = self
+			.handles
+			.borrow()
+			.iter()
+			.filter_map(|(target, handle)| {
+				let target_ref = target.upgrade()?;
+				match target_ref.is_connected(handle) {
+					true => unsafe {
+						let handle = handle.as_raw();
+						gobject_ffi::g_signal_handler_block(target_ref.as_object_ref().to_glib_none().0, handle);
+						Some((target.clone(), handle))
+					},
+					false => None,
+				}
+			})
+			.collect();
+		HandlesBlockGuard { handlers }
+	}
+}
+
+impl fmt::Debug for SignalHandles {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("SignalHandles").field("len", &self.len()).finish()
+	}
+}
+
+impl Drop for SignalHandles {
+	fn drop(&mut self) {
+		self.disconnect_all();
+	}
+}
+
+/// Keeps the handlers of a [SignalHandles] blocked until dropped.
+///
+/// Created by [SignalHandles::block_all].
+#[must_use]
+#[derive(Debug)]
+pub struct HandlesBlockGuard {
+	handlers: Vec<(WeakRef<glib::Object>, c_ulong)>,
+}
+
+impl HandlesBlockGuard {
+	/// The number of handlers blocked by this guard.
+	pub fn blocked(&self) -> usize {
+		self.handlers.len()
+	}
+}
+
+impl Drop for HandlesBlockGuard {
+	fn drop(&mut self) {
+		for (target, handle) in &self.handlers {
+			if let Some(target) = target.upgrade() {
+				if target.is_connected(&unsafe { from_glib(*handle) }) {
+					unsafe {
+						gobject_ffi::g_signal_handler_unblock(target.as_object_ref().to_glib_none().0, *handle);
+					}
+				}
+			}
+		}
+	}
+}
+
+fn downgrade<O: ObjectType>(target: &O) -> WeakRef<glib::Object> {
+	let target: glib::Object = unsafe { from_glib_none(target.as_object_ref().to_glib_none().0) };
+	target.downgrade()
+}
@@ -0,0 +1,65 @@
+use {
+	crate::{ConnectDetails, ConnectEof, DetailedSignal, FromValueOption, Notifies, ObjectSignalExt, ToValues},
+	futures_sink::Sink,
+	glib::{ObjectExt, ObjectType, WeakRef},
+	std::{
+		pin::Pin,
+		task::{Context, Poll},
+	},
+};
+
+/// Emits a signal for every item sent, the reverse of [SignalStream](crate::SignalStream).
+///
+/// Only holds onto its target weakly, and fails with [ConnectEof] once it has been finalized.
+/// The values returned by the signal's handlers are discarded.
+#[must_use]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+#[derive(Debug)]
+pub struct SignalSink<O: ObjectType, S> {
+	target: WeakRef<O>,
+	signal: ConnectDetails<S>,
+}
+
+impl<O: ObjectType, S: DetailedSignal> SignalSink<O, S> {
+	pub fn new<S_: Into<ConnectDetails<S>>>(target: &O, signal: S_) -> Self {
+		Self {
+			target: target.downgrade(),
+			signal: signal.into(),
+		}
+	}
+
+	pub fn target(&self) -> &WeakRef<O> {
+		&self.target
+	}
+}
+
+impl<O, S> Sink<S::Arguments> for SignalSink<O, S>
+where
+	O: ObjectSignalExt + Notifies<S::Signal>,
+	S: DetailedSignal,
+	S::Arguments: ToValues,
+	S::Return: FromValueOption,
+{
+	type Error = ConnectEof;
+
+	fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		Poll::Ready(match self.target.upgrade() {
+			Some(_) => Ok(()),
+			None => Err(ConnectEof),
+		})
+	}
+
+	fn start_send(self: Pin<&mut Self>, args: S::Arguments) -> Result<(), Self::Error> {
+		let target = self.target.upgrade().ok_or(ConnectEof)?;
+		target.emit_signal::<S, _>(self.signal, args);
+		Ok(())
+	}
+
+	fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		Poll::Ready(Ok(()))
+	}
+}
@@ -1,10 +1,29 @@
+#[cfg(feature = "gio")]
+use crate::cancellable::CancelHandle;
 use {
-	crate::{BorrowedObject, ConnectDetails, DetailedSignal, FromValues, ObjectSignalExt, ToValueOption},
+	crate::{
+		finalized::{finalized, take_until_finalized, TakeUntilFinalized},
+		stream_task::{ForEach, ForEachAsync, StreamTask},
+		timed::{self, Debounce, Sample, Throttle, Timer},
+		BorrowedObject, ConnectDetails, DetailedSignal, DynamicSignal, FromValues, Notifies, ObjectSignalExt,
+		ToValueOption,
+	},
 	futures_channel::mpsc,
 	futures_core::{ready, FusedFuture, FusedStream, Stream},
-	glib::{g_warning, value::FromValue, Closure, ObjectExt, ObjectType, SignalHandlerId, Value, WeakRef},
+	glib::{
+		g_warning, value::FromValue, BoolError, Cast, Closure, MainContext, ObjectExt, ObjectType, SignalHandlerId, Value,
+		WeakRef,
+	},
 	std::{
-		error::Error, fmt, future::Future, hint::unreachable_unchecked, io, mem::ManuallyDrop, pin::Pin, ptr, task::Poll,
+		error::Error,
+		fmt,
+		future::Future,
+		io,
+		mem::{self, ManuallyDrop},
+		pin::Pin,
+		ptr,
+		task::Poll,
+		time::Duration,
 	},
 };
 
@@ -14,7 +33,9 @@ use {
 pub struct SignalStream<O: ObjectType, T> {
 	rx: mpsc::UnboundedReceiver<T>,
 	target: WeakRef<O>,
-	handle: Option<SignalHandlerId>,
+	handles: Vec<SignalHandlerId>,
+	#[cfg(feature = "gio")]
+	cancel: Vec<CancelHandle>,
 }
 
 impl<O: ObjectType, T> SignalStream<O, T> {
@@ -44,18 +65,61 @@ impl<O: ObjectType, T> SignalStream<O, T> {
 		SignalStream {
 			rx,
 			target: target.downgrade(),
-			handle: Some(handle),
+			handles: vec![handle],
+			#[cfg(feature = "gio")]
+			cancel: Vec::new(),
+		}
+	}
+
+	/// Ends the stream once `cancellable` is triggered, disconnecting its handlers.
+	///
+	/// Items that were received but not yet polled are discarded. Cancelling from another thread
+	/// defers the disconnect to the thread-default main context this was called from.
+	#[cfg(feature = "gio")]
+	#[cfg_attr(feature = "dox", doc(cfg(feature = "gio")))]
+	pub fn with_cancellable(mut self, cancellable: &gio::Cancellable) -> Self {
+		if let Some(target) = self.target.upgrade() {
+			self.cancel = self
+				.handles
+				.iter()
+				.map(|handle| CancelHandle::disconnect_on_cancel(&target, handle, cancellable))
+				.collect();
+		}
+		self
+	}
+
+	/// Whether the stream ended because its cancellable was triggered.
+	pub fn is_cancelled(&self) -> bool {
+		#[cfg(feature = "gio")]
+		if let Some(cancel) = self.cancel.first() {
+			return cancel.is_cancelled()
 		}
+		false
 	}
 
 	pub fn once(self) -> OnceFuture<O, T> {
 		OnceFuture::new(self)
 	}
 
+	/// Like [once](SignalStream::once), but skips emissions until one satisfies `predicate`.
+	pub fn once_where<F: FnMut(&T) -> bool + Send + 'static>(self, predicate: F) -> OnceFuture<O, T> {
+		OnceFuture::new(self).with_predicate(predicate)
+	}
+
+	/// Like [once](SignalStream::once), but fails with [OnceError::Timeout] if the signal isn't
+	/// emitted within `duration`.
+	pub fn once_timeout(self, duration: Duration) -> OnceFuture<O, T> {
+		OnceFuture::new(self).with_timeout(duration)
+	}
+
 	pub fn disconnect(&mut self) {
-		if let Some(handle) = self.handle.take() {
-			if let Some(target) = self.target.upgrade() {
-				target.disconnect(handle);
+		let handles = mem::take(&mut self.handles);
+		if let Some(target) = self.target.upgrade() {
+			for handle in handles {
+				// the handler may already be gone if it was cancelled
+				if unsafe { target.unsafe_cast_ref::<glib::Object>() }.is_connected(&handle) {
+					target.disconnect(handle);
+				}
 			}
 		}
 	}
@@ -63,7 +127,14 @@ impl<O: ObjectType, T> SignalStream<O, T> {
 	pub fn into_target(self) -> WeakRef<O> {
 		let mut this = ManuallyDrop::new(self);
 		this.disconnect();
-		unsafe { ptr::read(&this.target) }
+		unsafe {
+			// everything but the target still needs to be dropped, or cancel handles stay connected
+			ptr::drop_in_place(&mut this.rx);
+			ptr::drop_in_place(&mut this.handles);
+			#[cfg(feature = "gio")]
+			ptr::drop_in_place(&mut this.cancel);
+			ptr::read(&this.target)
+		}
 	}
 
 	pub fn target(&self) -> &WeakRef<O> {
@@ -73,12 +144,146 @@ impl<O: ObjectType, T> SignalStream<O, T> {
 	pub fn attach_target(self) -> SignalStreamSelf<O, T> {
 		SignalStreamSelf::from(self)
 	}
+
+	/// Yields the most recent emission once the signal stops being emitted for `duration`.
+	///
+	/// Timers run on the thread-default main context of whichever thread polls the stream.
+	pub fn debounce(self, duration: Duration) -> Debounce<Self> {
+		Debounce::new(self, duration)
+	}
+
+	/// Yields an emission, then drops any others that follow within `duration`.
+	pub fn throttle(self, duration: Duration) -> Throttle<Self> {
+		Throttle::new(self, duration)
+	}
+
+	/// Yields the most recent emission every `duration`, skipping intervals without any.
+	pub fn sample(self, duration: Duration) -> Sample<Self> {
+		Sample::new(self, duration)
+	}
+
+	/// Ends the stream once `object` is finalized, which may be a different object than the target.
+	pub fn take_until_finalized<P: ObjectType>(self, object: &P) -> TakeUntilFinalized<Self> {
+		take_until_finalized(self, object)
+	}
+
+	/// Spawns a task on `context` that calls `f` with every emission until the stream ends.
+	///
+	/// The stream ends once the target is finalized, and the task is aborted when the returned
+	/// [StreamTask] is dropped. `context` must be owned by the current thread.
+	pub fn for_each_local<F: FnMut(T) + 'static>(self, context: &MainContext, f: F) -> StreamTask
+	where
+		T: 'static,
+		O: 'static,
+	{
+		StreamTask::spawn(context, ForEach::new(self, f))
+	}
+
+	/// Like [for_each_local](Self::for_each_local), but runs the future returned by `f` for every
+	/// emission, with at most `limit` running at once.
+	///
+	/// Emissions are left queued while the limit is reached. Once the target is finalized, queued
+	/// emissions are discarded and any futures that are still running are dropped, as they are when
+	/// the task is aborted.
+	///
+	/// # Panics
+	///
+	/// If `limit` is zero.
+	pub fn for_each_async<F, Fut>(self, context: &MainContext, limit: usize, f: F) -> StreamTask
+	where
+		F: FnMut(T) -> Fut + 'static,
+		Fut: Future<Output = ()> + 'static,
+		T: 'static,
+		O: 'static,
+	{
+		let finalized = self.target.upgrade().map(|target| finalized(&target));
+		StreamTask::spawn(context, ForEachAsync::new(self, limit, f, finalized))
+	}
+}
+
+impl<O: ObjectSignalExt> SignalStream<O, Vec<Value>> {
+	/// Connects to a signal that is only known at runtime, see
+	/// [handle_dynamic](ObjectSignalExt::handle_dynamic).
+	pub fn connect_dynamic(target: &O, signal: &DynamicSignal) -> Result<Self, BoolError> {
+		let (tx, rx) = futures_channel::mpsc::unbounded();
+		let name = signal.name();
+		let default_return = signal.default_return();
+		let handle = target.handle_dynamic(signal, move |_, args| {
+			if let Err(e) = tx.unbounded_send(args.to_vec()) {
+				g_warning!("glib-signal", "Failed to signal {:?}: {:?}", name, e);
+			}
+			default_return.clone()
+		})?;
+
+		Ok(SignalStream {
+			rx,
+			target: target.downgrade(),
+			handles: vec![handle],
+			#[cfg(feature = "gio")]
+			cancel: Vec::new(),
+		})
+	}
+}
+
+impl<O: ObjectSignalExt, T: 'static> SignalStream<O, T> {
+	/// Merges several signals of `target` into a single stream, see
+	/// [signal_enum](crate::signal_enum).
+	pub fn connect_merged<F: FnOnce(&mut MergedSignals<O, T>)>(target: &O, connect: F) -> Self {
+		let (tx, rx) = futures_channel::mpsc::unbounded();
+		let mut signals = MergedSignals {
+			target,
+			tx,
+			handles: Vec::new(),
+		};
+		connect(&mut signals);
+
+		SignalStream {
+			rx,
+			target: target.downgrade(),
+			handles: signals.handles,
+			#[cfg(feature = "gio")]
+			cancel: Vec::new(),
+		}
+	}
+}
+
+/// Connects the signals of a stream created by [SignalStream::connect_merged].
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+#[derive(Debug)]
+pub struct MergedSignals<'a, O, T> {
+	target: &'a O,
+	tx: mpsc::UnboundedSender<T>,
+	handles: Vec<SignalHandlerId>,
+}
+
+impl<'a, O: ObjectSignalExt, T: 'static> MergedSignals<'a, O, T> {
+	/// Sends the arguments of every emission of `signal` to the stream, converted by `map`.
+	pub fn signal<S, S_>(&mut self, signal: S_, map: fn(S::Arguments) -> T)
+	where
+		S: DetailedSignal,
+		S_: Into<ConnectDetails<S>>,
+		O: Notifies<S::Signal>,
+		<S::Return as ToValueOption>::Type: Default,
+	{
+		let signal = signal.into();
+		let tx = self.tx.clone();
+		let handle = self.target.handle_local::<S, _, _>(signal, move |_, args| {
+			if let Err(e) = tx.unbounded_send(map(args)) {
+				g_warning!("glib-signal", "Failed to signal {:?}: {:?}", signal, e);
+			}
+			Default::default()
+		});
+		self.handles.push(handle);
+	}
 }
 
 impl<O: ObjectType, T> Stream for SignalStream<O, T> {
 	type Item = T;
 
 	fn poll_next(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+		if self.is_cancelled() {
+			return Poll::Ready(None)
+		}
 		let rx = unsafe { self.map_unchecked_mut(|s| &mut s.rx) };
 		rx.poll_next(cx)
 	}
@@ -90,7 +295,7 @@ impl<O: ObjectType, T> Stream for SignalStream<O, T> {
 
 impl<O: ObjectType, T> FusedStream for SignalStream<O, T> {
 	fn is_terminated(&self) -> bool {
-		self.rx.is_terminated()
+		self.is_cancelled() || self.rx.is_terminated()
 	}
 }
 
@@ -123,13 +328,92 @@ impl From<ConnectEof> for glib::Error {
 	}
 }
 
+/// The reason a [OnceFuture] completed without receiving a signal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OnceError {
+	/// The handler was disconnected, usually because the target was finalized.
+	Eof,
+	/// The stream's cancellable was triggered, see [SignalStream::with_cancellable].
+	Cancelled,
+	/// The signal wasn't emitted in time, see [SignalStream::once_timeout].
+	Timeout,
+}
+
+impl fmt::Display for OnceError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			OnceError::Eof => fmt::Display::fmt(&ConnectEof, f),
+			OnceError::Cancelled => write!(f, "signal stream cancelled"),
+			OnceError::Timeout => write!(f, "timed out waiting for signal"),
+		}
+	}
+}
+
+impl Error for OnceError {}
+
+impl From<ConnectEof> for OnceError {
+	fn from(_: ConnectEof) -> Self {
+		OnceError::Eof
+	}
+}
+
+impl From<OnceError> for io::Error {
+	fn from(e: OnceError) -> Self {
+		match e {
+			OnceError::Eof => ConnectEof.into(),
+			OnceError::Cancelled => io::Error::new(io::ErrorKind::Interrupted, e),
+			OnceError::Timeout => io::Error::new(io::ErrorKind::TimedOut, e),
+		}
+	}
+}
+
+impl From<OnceError> for glib::Error {
+	fn from(e: OnceError) -> Self {
+		match e {
+			OnceError::Eof => ConnectEof.into(),
+			#[cfg(feature = "gio")]
+			OnceError::Cancelled => glib::Error::new(gio::IOErrorEnum::Cancelled, &format!("{:?}", e)),
+			#[cfg(not(feature = "gio"))]
+			OnceError::Cancelled => glib::Error::new(glib::FileError::Intr, &format!("{:?}", e)),
+			#[cfg(feature = "gio")]
+			OnceError::Timeout => glib::Error::new(gio::IOErrorEnum::TimedOut, &format!("{:?}", e)),
+			#[cfg(not(feature = "gio"))]
+			OnceError::Timeout => glib::Error::new(glib::FileError::Failed, &format!("{:?}", e)),
+		}
+	}
+}
+
+type Predicate<T> = Box<dyn FnMut(&T) -> bool + Send>;
+
 pub struct OnceFuture<O: ObjectType, T> {
 	stream: Option<SignalStream<O, T>>,
+	predicate: Option<Predicate<T>>,
+	timer: Option<Timer>,
 }
 
 impl<O: ObjectType, T> OnceFuture<O, T> {
 	pub fn new(stream: SignalStream<O, T>) -> Self {
-		Self { stream: Some(stream) }
+		Self {
+			stream: Some(stream),
+			predicate: None,
+			timer: None,
+		}
+	}
+
+	/// Skips emissions until one satisfies `predicate`.
+	///
+	/// `predicate` must be `Send` so that the future can still be spawned on other threads.
+	pub fn with_predicate<F: FnMut(&T) -> bool + Send + 'static>(mut self, predicate: F) -> Self {
+		self.predicate = Some(Box::new(predicate));
+		self
+	}
+
+	/// Fails with [OnceError::Timeout] unless a matching emission arrives within `duration`.
+	///
+	/// The timer runs on the thread-default main context of whichever thread polls the future.
+	pub fn with_timeout(mut self, duration: Duration) -> Self {
+		self.timer = Some(timed::timer(duration));
+		self
 	}
 
 	/// check `is_terminated` first!
@@ -139,26 +423,30 @@ impl<O: ObjectType, T> OnceFuture<O, T> {
 }
 
 impl<O: ObjectType, T> Future for OnceFuture<O, T> {
-	type Output = Result<(T, WeakRef<O>), ConnectEof>;
+	type Output = Result<(T, WeakRef<O>), OnceError>;
 
 	fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-		//let this = unsafe { self.get_unchecked_mut() };
-		let (res, stream) = unsafe {
-			let mut stream = self.map_unchecked_mut(|this| &mut this.stream);
-			let res = match stream.as_mut().as_pin_mut() {
-				Some(stream) => ready!(stream.poll_next(cx)),
-				None => return Poll::Pending,
-			};
-			(res, match stream.get_unchecked_mut().take() {
-				Some(s) => s,
-				None => unreachable_unchecked(),
-			})
+		let this = self.get_mut();
+		let stream = match &mut this.stream {
+			Some(stream) => stream,
+			None => return Poll::Pending,
 		};
-		let obj = stream.into_target();
-		Poll::Ready(match res {
-			Some(res) => Ok((res, obj)),
-			None => Err(ConnectEof),
-		})
+		let res = loop {
+			match Pin::new(&mut *stream).poll_next(cx) {
+				Poll::Ready(Some(res)) => match this.predicate.as_mut().map(|predicate| predicate(&res)) {
+					Some(false) => continue,
+					_ => break Ok(res),
+				},
+				Poll::Ready(None) if stream.is_cancelled() => break Err(OnceError::Cancelled),
+				Poll::Ready(None) => break Err(ConnectEof.into()),
+				Poll::Pending => match timed::poll_timer(&mut this.timer, cx) {
+					true => break Err(OnceError::Timeout),
+					false => return Poll::Pending,
+				},
+			}
+		};
+		let obj = this.stream.take().unwrap().into_target();
+		Poll::Ready(res.map(|res| (res, obj)))
 	}
 }
 
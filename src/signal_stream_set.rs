@@ -0,0 +1,178 @@
+use {
+	crate::{ConnectDetails, DetailedSignal, Notifies, ObjectSignalExt, ToValueOption},
+	futures_channel::mpsc,
+	futures_core::Stream,
+	glib::{g_warning, Cast, ObjectExt, ObjectType, SignalHandlerId, WeakRef},
+	std::{
+		fmt,
+		pin::Pin,
+		task::{Context, Poll},
+	},
+};
+
+struct Member<O: ObjectType> {
+	target: WeakRef<O>,
+	handle: SignalHandlerId,
+}
+
+/// A stream of one signal across a changing set of objects.
+///
+/// Each item is tagged with the object that emitted it, or with the key it was inserted with.
+/// Objects are only held weakly, and are removed from the set once finalized. The stream never
+/// ends on its own.
+#[must_use]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+pub struct SignalStreamSet<O: ObjectType, S: DetailedSignal, K = O> {
+	signal: ConnectDetails<S>,
+	tx: mpsc::UnboundedSender<(K, S::Arguments)>,
+	rx: mpsc::UnboundedReceiver<(K, S::Arguments)>,
+	members: Vec<Member<O>>,
+}
+
+impl<O, S, K> SignalStreamSet<O, S, K>
+where
+	O: ObjectSignalExt + Notifies<S::Signal>,
+	S: DetailedSignal,
+	K: 'static,
+	<S::Return as ToValueOption>::Type: Default,
+{
+	pub fn new<S_: Into<ConnectDetails<S>>>(signal: S_) -> Self {
+		let (tx, rx) = mpsc::unbounded();
+		Self {
+			signal: signal.into(),
+			tx,
+			rx,
+			members: Vec::new(),
+		}
+	}
+
+	/// Adds `target` to the set, tagging its emissions with `key`.
+	///
+	/// Returns `false` without changing its key if `target` was already in the set.
+	pub fn insert_keyed(&mut self, target: &O, key: K) -> bool
+	where
+		K: Clone,
+	{
+		self.connect(target, move |_| key.clone())
+	}
+
+	/// Removes `target` from the set, returning whether it was present.
+	pub fn remove(&mut self, target: &O) -> bool {
+		self.prune();
+		match self.position(target) {
+			Some(i) => {
+				let member = self.members.swap_remove(i);
+				if target.is_connected(&member.handle) {
+					target.disconnect(member.handle);
+				}
+				true
+			},
+			None => false,
+		}
+	}
+
+	fn connect<F: Fn(&O) -> K + 'static>(&mut self, target: &O, key: F) -> bool {
+		self.prune();
+		if self.contains(target) {
+			return false
+		}
+		let signal = self.signal;
+		let tx = self.tx.clone();
+		let handle = target.handle_local::<S, _, _>(signal, move |this, args| {
+			if let Err(e) = tx.unbounded_send((key(this), args)) {
+				g_warning!("glib-signal", "Failed to signal {:?}: {:?}", signal, e);
+			}
+			Default::default()
+		});
+		self.members.push(Member {
+			target: target.downgrade(),
+			handle,
+		});
+		true
+	}
+}
+
+impl<O: ObjectType, S: DetailedSignal, K> SignalStreamSet<O, S, K> {
+	pub fn contains(&self, target: &O) -> bool {
+		self.position(target).is_some()
+	}
+
+	/// The number of objects in the set that haven't been finalized.
+	pub fn len(&self) -> usize {
+		self
+			.members
+			.iter()
+			.filter(|member| member.target.upgrade().is_some())
+			.count()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// The objects in the set that haven't been finalized.
+	pub fn targets(&self) -> impl Iterator<Item = O> + '_ {
+		self.members.iter().filter_map(|member| member.target.upgrade())
+	}
+
+	/// Removes every object from the set.
+	pub fn clear(&mut self) {
+		for member in self.members.drain(..) {
+			if let Some(target) = member.target.upgrade() {
+				if unsafe { target.unsafe_cast_ref::<glib::Object>() }.is_connected(&member.handle) {
+					target.disconnect(member.handle);
+				}
+			}
+		}
+	}
+
+	fn position(&self, target: &O) -> Option<usize> {
+		self
+			.members
+			.iter()
+			.position(|member| member.target.upgrade().as_ref() == Some(target))
+	}
+
+	/// Forgets the handlers of finalized objects, which have already been disconnected.
+	fn prune(&mut self) {
+		self.members.retain(|member| member.target.upgrade().is_some())
+	}
+}
+
+impl<O, S> SignalStreamSet<O, S, O>
+where
+	O: ObjectSignalExt + Notifies<S::Signal>,
+	S: DetailedSignal,
+	<S::Return as ToValueOption>::Type: Default,
+{
+	/// Adds `target` to the set, tagging its emissions with the object itself.
+	///
+	/// Returns `false` if `target` was already in the set.
+	pub fn insert(&mut self, target: &O) -> bool {
+		self.connect(target, |this| this.clone())
+	}
+}
+
+impl<O: ObjectType, S: DetailedSignal, K> Stream for SignalStreamSet<O, S, K> {
+	type Item = (K, S::Arguments);
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let rx = unsafe { self.map_unchecked_mut(|s| &mut s.rx) };
+		rx.poll_next(cx)
+	}
+}
+
+impl<O: ObjectType, S: DetailedSignal, K> Drop for SignalStreamSet<O, S, K> {
+	fn drop(&mut self) {
+		self.clear();
+	}
+}
+
+impl<O: ObjectType, S: DetailedSignal, K> fmt::Debug for SignalStreamSet<O, S, K> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("SignalStreamSet")
+			.field("signal", &self.signal)
+			.field("len", &self.members.len())
+			.finish()
+	}
+}
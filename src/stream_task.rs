@@ -0,0 +1,187 @@
+use {
+	crate::Finalized,
+	futures_core::{FusedFuture, Stream},
+	glib::{JoinError, JoinHandle, MainContext},
+	std::{
+		fmt,
+		future::Future,
+		pin::Pin,
+		task::{Context, Poll},
+	},
+};
+
+/// A task consuming a stream, which is aborted when dropped.
+///
+/// Created by [SignalStream::for_each_local](crate::SignalStream::for_each_local) or
+/// [SignalStream::for_each_async](crate::SignalStream::for_each_async). Awaiting it resolves once
+/// the stream has ended.
+#[must_use = "the task is aborted when dropped"]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+pub struct StreamTask {
+	handle: Option<JoinHandle<()>>,
+}
+
+impl StreamTask {
+	pub(crate) fn spawn<F: Future<Output = ()> + 'static>(context: &MainContext, future: F) -> Self {
+		Self {
+			handle: Some(context.spawn_local(future)),
+		}
+	}
+
+	/// Whether the stream has ended, or the task was otherwise removed from its main context.
+	pub fn is_finished(&self) -> bool {
+		self
+			.handle
+			.as_ref()
+			.map(|handle| handle.source().is_destroyed())
+			.unwrap_or(true)
+	}
+
+	pub fn abort(&self) {
+		if let Some(handle) = &self.handle {
+			handle.abort();
+		}
+	}
+
+	/// Lets the task keep running after this is dropped.
+	pub fn detach(mut self) -> JoinHandle<()> {
+		self.handle.take().unwrap()
+	}
+}
+
+impl Future for StreamTask {
+	type Output = Result<(), JoinError>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		match &mut self.get_mut().handle {
+			Some(handle) => Pin::new(handle).poll(cx),
+			None => panic!("StreamTask polled after detach"),
+		}
+	}
+}
+
+impl FusedFuture for StreamTask {
+	fn is_terminated(&self) -> bool {
+		self
+			.handle
+			.as_ref()
+			.map(|handle| handle.is_terminated())
+			.unwrap_or(true)
+	}
+}
+
+impl Drop for StreamTask {
+	fn drop(&mut self) {
+		self.abort();
+	}
+}
+
+impl fmt::Debug for StreamTask {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("StreamTask")
+			.field("source", &self.handle.as_ref().map(|handle| handle.source()))
+			.finish()
+	}
+}
+
+/// Calls `f` with every item of `stream`.
+pub(crate) struct ForEach<St, F> {
+	stream: St,
+	f: F,
+}
+
+impl<St, F> ForEach<St, F> {
+	pub(crate) fn new(stream: St, f: F) -> Self {
+		Self { stream, f }
+	}
+}
+
+// `f` is never pinned
+impl<St: Unpin, F> Unpin for ForEach<St, F> {}
+
+impl<St: Stream + Unpin, F: FnMut(St::Item)> Future for ForEach<St, F> {
+	type Output = ();
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		loop {
+			match Pin::new(&mut this.stream).poll_next(cx) {
+				Poll::Ready(Some(item)) => (this.f)(item),
+				Poll::Ready(None) => return Poll::Ready(()),
+				Poll::Pending => return Poll::Pending,
+			}
+		}
+	}
+}
+
+/// Runs the future returned by `f` for every item of `stream`, with at most `limit` running at
+/// once.
+///
+/// Futures that are still running when the stream ends or `finalized` resolves are dropped.
+pub(crate) struct ForEachAsync<St, F, Fut> {
+	stream: St,
+	f: F,
+	limit: usize,
+	running: Vec<Pin<Box<Fut>>>,
+	finalized: Option<Finalized>,
+}
+
+impl<St, F, Fut> ForEachAsync<St, F, Fut> {
+	pub(crate) fn new(stream: St, limit: usize, f: F, finalized: Option<Finalized>) -> Self {
+		assert!(limit > 0, "for_each_async requires a limit of at least 1");
+		Self {
+			stream,
+			f,
+			limit,
+			running: Vec::new(),
+			finalized,
+		}
+	}
+}
+
+impl<St: Unpin, F, Fut> Unpin for ForEachAsync<St, F, Fut> {}
+
+impl<St, F, Fut> Future for ForEachAsync<St, F, Fut>
+where
+	St: Stream + Unpin,
+	F: FnMut(St::Item) -> Fut,
+	Fut: Future<Output = ()>,
+{
+	type Output = ();
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.get_mut();
+		// the stream isn't polled while the limit is reached, so it can't be relied on to notice
+		if let Some(finalized) = &mut this.finalized {
+			if Pin::new(finalized).poll(cx).is_ready() {
+				this.running.clear();
+				return Poll::Ready(())
+			}
+		}
+		loop {
+			while this.running.len() < this.limit {
+				match Pin::new(&mut this.stream).poll_next(cx) {
+					Poll::Ready(Some(item)) => this.running.push(Box::pin((this.f)(item))),
+					Poll::Ready(None) => {
+						this.running.clear();
+						return Poll::Ready(())
+					},
+					Poll::Pending => break,
+				}
+			}
+
+			let running = this.running.len();
+			let mut i = 0;
+			while i < this.running.len() {
+				match this.running[i].as_mut().poll(cx) {
+					Poll::Ready(()) => drop(this.running.swap_remove(i)),
+					Poll::Pending => i += 1,
+				}
+			}
+			// only a completed future can make room to accept more items
+			if this.running.len() == running {
+				return Poll::Pending
+			}
+		}
+	}
+}
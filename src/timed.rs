@@ -0,0 +1,266 @@
+use {
+	futures_core::{FusedStream, Stream},
+	std::{
+		fmt,
+		future::Future,
+		pin::Pin,
+		task::{Context, Poll},
+		time::Duration,
+	},
+};
+
+pub(crate) type Timer = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Starts a timer on the thread-default main context, which is attached once first polled.
+pub(crate) fn timer(duration: Duration) -> Timer {
+	glib::timeout_future(duration)
+}
+
+/// Polls `timer`, clearing it once it has fired.
+pub(crate) fn poll_timer(timer: &mut Option<Timer>, cx: &mut Context<'_>) -> bool {
+	match timer.as_mut().map(|t| t.as_mut().poll(cx)) {
+		Some(Poll::Ready(())) => {
+			*timer = None;
+			true
+		},
+		_ => false,
+	}
+}
+
+/// Yields the most recent item once no new items have arrived for a while.
+///
+/// Created by [SignalStream::debounce](crate::SignalStream::debounce). Any pending item is
+/// yielded immediately when the inner stream ends.
+#[must_use]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+pub struct Debounce<St: Stream> {
+	stream: St,
+	duration: Duration,
+	pending: Option<St::Item>,
+	timer: Option<Timer>,
+	done: bool,
+}
+
+impl<St: Stream> Debounce<St> {
+	pub fn new(stream: St, duration: Duration) -> Self {
+		Self {
+			stream,
+			duration,
+			pending: None,
+			timer: None,
+			done: false,
+		}
+	}
+
+	pub fn into_inner(self) -> St {
+		self.stream
+	}
+}
+
+impl<St: Stream + Unpin> Stream for Debounce<St> {
+	type Item = St::Item;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+		while !this.done {
+			match Pin::new(&mut this.stream).poll_next(cx) {
+				Poll::Ready(Some(item)) => {
+					this.pending = Some(item);
+					this.timer = Some(timer(this.duration));
+				},
+				Poll::Ready(None) => this.done = true,
+				Poll::Pending => break,
+			}
+		}
+		if this.done {
+			this.timer = None;
+			return Poll::Ready(this.pending.take())
+		}
+		match poll_timer(&mut this.timer, cx) {
+			true => Poll::Ready(this.pending.take()),
+			false => Poll::Pending,
+		}
+	}
+}
+
+impl<St: Stream + Unpin> FusedStream for Debounce<St> {
+	fn is_terminated(&self) -> bool {
+		self.done && self.pending.is_none()
+	}
+}
+
+// the pending item is never pinned
+impl<St: Stream + Unpin> Unpin for Debounce<St> {}
+
+impl<St: Stream + fmt::Debug> fmt::Debug for Debounce<St> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Debounce")
+			.field("stream", &self.stream)
+			.field("duration", &self.duration)
+			.finish()
+	}
+}
+
+/// Yields an item, then drops any others that arrive for a while.
+///
+/// Created by [SignalStream::throttle](crate::SignalStream::throttle). Items are dropped as they
+/// are polled, so those that arrived before an item is yielded are dropped along with it.
+#[must_use]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+pub struct Throttle<St> {
+	stream: St,
+	duration: Duration,
+	timer: Option<Timer>,
+	done: bool,
+}
+
+impl<St: Stream> Throttle<St> {
+	pub fn new(stream: St, duration: Duration) -> Self {
+		Self {
+			stream,
+			duration,
+			timer: None,
+			done: false,
+		}
+	}
+
+	pub fn into_inner(self) -> St {
+		self.stream
+	}
+}
+
+impl<St: Stream + Unpin> Throttle<St> {
+	/// Drops every item that is immediately available.
+	fn drain(&mut self, cx: &mut Context<'_>) {
+		while !self.done {
+			match Pin::new(&mut self.stream).poll_next(cx) {
+				Poll::Ready(Some(_)) => (),
+				Poll::Ready(None) => self.done = true,
+				Poll::Pending => break,
+			}
+		}
+	}
+}
+
+impl<St: Stream + Unpin> Stream for Throttle<St> {
+	type Item = St::Item;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+		if !poll_timer(&mut this.timer, cx) && this.timer.is_some() {
+			this.drain(cx);
+		}
+		if this.done {
+			return Poll::Ready(None)
+		}
+		if this.timer.is_some() {
+			return Poll::Pending
+		}
+		match Pin::new(&mut this.stream).poll_next(cx) {
+			Poll::Ready(Some(item)) => {
+				this.timer = Some(timer(this.duration));
+				poll_timer(&mut this.timer, cx);
+				this.drain(cx);
+				Poll::Ready(Some(item))
+			},
+			Poll::Ready(None) => {
+				this.done = true;
+				Poll::Ready(None)
+			},
+			Poll::Pending => Poll::Pending,
+		}
+	}
+}
+
+impl<St: Stream + Unpin> FusedStream for Throttle<St> {
+	fn is_terminated(&self) -> bool {
+		self.done
+	}
+}
+
+impl<St: fmt::Debug> fmt::Debug for Throttle<St> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Throttle")
+			.field("stream", &self.stream)
+			.field("duration", &self.duration)
+			.finish()
+	}
+}
+
+/// Yields the most recent item at regular intervals, if any arrived since the last one.
+///
+/// Created by [SignalStream::sample](crate::SignalStream::sample). The interval starts when the
+/// stream is first polled.
+#[must_use]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "futures")))]
+pub struct Sample<St: Stream> {
+	stream: St,
+	duration: Duration,
+	latest: Option<St::Item>,
+	timer: Option<Timer>,
+	done: bool,
+}
+
+impl<St: Stream> Sample<St> {
+	pub fn new(stream: St, duration: Duration) -> Self {
+		Self {
+			stream,
+			duration,
+			latest: None,
+			timer: None,
+			done: false,
+		}
+	}
+
+	pub fn into_inner(self) -> St {
+		self.stream
+	}
+}
+
+impl<St: Stream + Unpin> Stream for Sample<St> {
+	type Item = St::Item;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+		while !this.done {
+			match Pin::new(&mut this.stream).poll_next(cx) {
+				Poll::Ready(Some(item)) => this.latest = Some(item),
+				Poll::Ready(None) => this.done = true,
+				Poll::Pending => break,
+			}
+		}
+		if this.done {
+			this.timer = None;
+			return Poll::Ready(this.latest.take())
+		}
+		loop {
+			if this.timer.is_none() {
+				this.timer = Some(timer(this.duration));
+			}
+			if !poll_timer(&mut this.timer, cx) {
+				return Poll::Pending
+			}
+			if let Some(item) = this.latest.take() {
+				return Poll::Ready(Some(item))
+			}
+		}
+	}
+}
+
+impl<St: Stream + Unpin> FusedStream for Sample<St> {
+	fn is_terminated(&self) -> bool {
+		self.done && self.latest.is_none()
+	}
+}
+
+// the latest item is never pinned
+impl<St: Stream + Unpin> Unpin for Sample<St> {}
+
+impl<St: Stream + fmt::Debug> fmt::Debug for Sample<St> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Sample")
+			.field("stream", &self.stream)
+			.field("duration", &self.duration)
+			.finish()
+	}
+}
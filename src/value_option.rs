@@ -1,11 +1,43 @@
-use glib::{value::ToValue, Value};
+use {
+	crate::AccumulatorFn,
+	glib::{
+		value::{FromValue, ToValue, ValueTypeChecker, ValueTypeMismatchError, ValueTypeMismatchOrNoneError},
+		BoolError, Type, Value,
+	},
+};
 
 pub trait ToValueOption: Sized {
 	type Type: Into<Self>;
 
+	/// The default [Signal::ACCUMULATOR](crate::Signal::ACCUMULATOR) for signals returning this type.
+	const ACCUMULATOR: Option<AccumulatorFn> = None;
+
 	fn to_value_option(self) -> Option<Value>;
 }
 
+/// The inverse of [ToValueOption], used to recover a typed return value
+/// from a signal emission or handler.
+pub trait FromValueOption: ToValueOption {
+	fn from_value_option(
+		value: Option<&Value>,
+	) -> Result<Self::Type, ValueTypeMismatchOrNoneError<ValueTypeMismatchError>>;
+}
+
+/// Fails unless `R` can represent the zero value of `ty`, which is what emitting a signal
+/// returns when no handler provides a value.
+pub(crate) fn check_zero_value<R: FromValueOption>(ty: Type) -> Result<(), BoolError> {
+	if ty == Type::UNIT {
+		return Ok(())
+	}
+	match R::from_value_option(Some(&Value::from_type(ty))) {
+		Ok(_) => Ok(()),
+		Err(_) => Err(glib::bool_error!(
+			"return type {} starts out as NULL, and must be declared as an Option",
+			ty
+		)),
+	}
+}
+
 pub struct PrimitiveValue<T>(T);
 impl<T> From<T> for PrimitiveValue<T> {
 	fn from(v: T) -> Self {
@@ -38,6 +70,36 @@ impl ToValueOption for PrimitiveValue<usize> {
 	}
 }
 
+impl FromValueOption for PrimitiveValue<()> {
+	fn from_value_option(
+		_value: Option<&Value>,
+	) -> Result<Self::Type, ValueTypeMismatchOrNoneError<ValueTypeMismatchError>> {
+		Ok(())
+	}
+}
+impl FromValueOption for PrimitiveValue<usize> {
+	#[cfg(target_pointer_width = "16")]
+	fn from_value_option(
+		value: Option<&Value>,
+	) -> Result<Self::Type, ValueTypeMismatchOrNoneError<ValueTypeMismatchError>> {
+		<u16 as FromValueOption>::from_value_option(value).map(|v| v as usize)
+	}
+
+	#[cfg(target_pointer_width = "32")]
+	fn from_value_option(
+		value: Option<&Value>,
+	) -> Result<Self::Type, ValueTypeMismatchOrNoneError<ValueTypeMismatchError>> {
+		<u32 as FromValueOption>::from_value_option(value).map(|v| v as usize)
+	}
+
+	#[cfg(target_pointer_width = "64")]
+	fn from_value_option(
+		value: Option<&Value>,
+	) -> Result<Self::Type, ValueTypeMismatchOrNoneError<ValueTypeMismatchError>> {
+		<u64 as FromValueOption>::from_value_option(value).map(|v| v as usize)
+	}
+}
+
 impl<T: ToValue> ToValueOption for T {
 	type Type = T;
 
@@ -45,3 +107,19 @@ impl<T: ToValue> ToValueOption for T {
 		Some(ToValue::to_value(&self))
 	}
 }
+
+impl<T> FromValueOption for T
+where
+	T: ToValue + for<'a> FromValue<'a>,
+	for<'a> ValueTypeMismatchOrNoneError<ValueTypeMismatchError>:
+		From<<<T as FromValue<'a>>::Checker as ValueTypeChecker>::Error>,
+{
+	fn from_value_option(
+		value: Option<&Value>,
+	) -> Result<Self::Type, ValueTypeMismatchOrNoneError<ValueTypeMismatchError>> {
+		match value {
+			Some(value) => Ok(value.get()?),
+			None => Err(ValueTypeMismatchOrNoneError::UnexpectedNone),
+		}
+	}
+}